@@ -0,0 +1,48 @@
+//abilities.rs
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use serde::{Deserialize, Serialize};
+
+//movement abilities unlocked by collectibles, granted by name from the "Ability" field in LDtk
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
+pub enum Ability {
+    DoubleJump,
+    WallClimb,
+    Grapple,
+}
+
+impl Ability {
+    pub fn from_name(name: &str) -> Option<Ability> {
+        match name {
+            "DoubleJump" => Some(Ability::DoubleJump),
+            "WallClimb" => Some(Ability::WallClimb),
+            "Grapple" => Some(Ability::Grapple),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Default, Debug, Component)]
+pub struct Abilities {
+    unlocked: HashSet<Ability>,
+}
+
+impl Abilities {
+    pub fn has(&self, ability: Ability) -> bool {
+        self.unlocked.contains(&ability)
+    }
+
+    // Returns false if the ability was already unlocked
+    pub fn grant(&mut self, ability: Ability) -> bool {
+        self.unlocked.insert(ability)
+    }
+
+    // Returns false if the ability wasn't unlocked
+    pub fn revoke(&mut self, ability: Ability) -> bool {
+        self.unlocked.remove(&ability)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Ability> + '_ {
+        self.unlocked.iter().copied()
+    }
+}
@@ -16,6 +16,8 @@ pub enum AnimationType {
     Run,
     Jump,
     Grapple,
+    WallSlide,
+    Death,
 }
 
 #[derive(Resource)]
@@ -23,6 +25,8 @@ pub struct AnimationAssets {
     layouts: HashMap<AnimationType, Handle<TextureAtlasLayout>>,
     textures: HashMap<AnimationType, Handle<Image>>,
     timers: HashMap<AnimationType, Timer>,
+    // Drawn size for sheets whose frames aren't the player's usual 32x32
+    custom_sizes: HashMap<AnimationType, Vec2>,
 }
 impl AnimationAssets {
     pub(crate) fn get_layout(&self, animation_type: AnimationType) -> Option<&Handle<TextureAtlasLayout>> {
@@ -33,6 +37,10 @@ impl AnimationAssets {
         self.textures.get(&animation_type)
     }
 
+    pub(crate) fn get_custom_size(&self, animation_type: AnimationType) -> Option<Vec2> {
+        self.custom_sizes.get(&animation_type).copied()
+    }
+
     pub(crate) fn get_timer_mut(&mut self, animation_type: AnimationType) -> Option<&mut Timer> {
         self.timers.get_mut(&animation_type)
     }
@@ -47,6 +55,7 @@ fn setup_animation_assets(
     let mut layouts = HashMap::new();
     let mut textures = HashMap::new();
     let mut timers = HashMap::new();
+    let mut custom_sizes = HashMap::new();
 
     // Load textures and create layouts for each animation type
     let mut load_animation = |anim_type: AnimationType, path: String, columns: u32, rows: u32, frame_duration: f32| {
@@ -65,9 +74,18 @@ fn setup_animation_assets(
     load_animation(AnimationType::Run, "man_walk_spritesheet.png".to_string(), 3, 2, 0.1);
     load_animation(AnimationType::Idle, "man_jump_spritesheet.png".to_string(), 1, 1, 0.0); // Use a single-frame texture for Idle.
     load_animation(AnimationType::Grapple, "man_grapple.png".to_string(), 3, 1, 0.0); // Use a single-frame texture for Idle.
+    load_animation(AnimationType::WallSlide, "man_grapple.png".to_string(), 3, 1, 0.15); // Arms up, clinging to the wall
+
+    // The death sheet has 80x80 frames, one direction per row. Only the first row is used and it plays once.
+    // Its character is drawn at a bit over half the man's height, so the frames are scaled up to match
+    textures.insert(AnimationType::Death, asset_server.load("misc/Base Character - Free/Base Character - Free/death.png"));
+    let death_layout = TextureAtlasLayout::from_grid(UVec2::new(80, 80), 6, 1, None, None);
+    layouts.insert(AnimationType::Death, texture_atlases.add(death_layout));
+    timers.insert(AnimationType::Death, Timer::from_seconds(0.1, TimerMode::Repeating));
+    custom_sizes.insert(AnimationType::Death, Vec2::splat(80.0 * 1.75));
 
     // Add more animations as needed
-    commands.insert_resource(AnimationAssets { layouts, textures, timers });
+    commands.insert_resource(AnimationAssets { layouts, textures, timers, custom_sizes });
 }
 
 pub struct PlayerAnimationPlugin;
@@ -0,0 +1,269 @@
+//camera.rs
+use crate::game_menu::GameState;
+use crate::grapple::{Grapple, HookState};
+use crate::player::Player;
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+// Rates are per second and go through smoothing() so the camera feels the same at any frame rate
+const FOLLOW_RATE: f32 = 6.0;
+// How quickly the clamp rectangle slides over to a newly selected level
+const BOUNDS_BLEND_RATE: f32 = 4.0;
+// Half size of the box the player can move in without the camera following
+const DEAD_ZONE: Vec2 = Vec2::new(24.0, 40.0);
+// Teleports (respawns, restarts, the tp command) cut straight to the player instead of panning
+const SNAP_DISTANCE: f32 = 600.0;
+// The camera leads the player by this many seconds of their velocity, further while swinging or riding a zipline
+const LOOK_AHEAD_TIME: f32 = 0.25;
+const GRAPPLE_LOOK_AHEAD_TIME: f32 = 0.45;
+const MAX_LOOK_AHEAD: f32 = 96.0;
+const LOOK_AHEAD_RATE: f32 = 3.0;
+// Vertically it only leads downwards, so a fall shows what's below
+const FALL_LOOK_AHEAD_TIME: f32 = 0.3;
+const MAX_FALL_LOOK_AHEAD: f32 = 120.0;
+// Trauma goes from 0 to 1 and drains over time, the shake grows with its square
+const TRAUMA_DECAY: f32 = 1.2;
+const MAX_SHAKE_OFFSET: f32 = 10.0;
+const MAX_SHAKE_ROLL: f32 = 0.04;
+const SHAKE_FREQUENCY: f32 = 30.0;
+// Camera scale outside of camera zones, and how fast it blends into a zone's
+pub const DEFAULT_ZOOM: f32 = 0.5;
+const ZOOM_BLEND_RATE: f32 = 3.0;
+// Point fields are stored in cells of the LDtk Entities layer
+const ENTITY_GRID_SIZE: i32 = 16;
+
+// The rectangle the camera view is kept inside, eased towards the selected level's bounds
+// so crossing into a neighbouring level pans instead of snapping
+#[derive(Resource, Default)]
+pub struct CameraBounds {
+    rect: Option<Rect>,
+}
+
+// Follow state, kept apart from the camera's transform so the shake never feeds back into the follow
+#[derive(Resource, Default)]
+pub struct CameraRig {
+    // Center of the dead-zone, dragged along by the player when they push against its edges
+    focus: Option<Vec2>,
+    look_ahead: Vec2,
+    position: Vec2,
+    trauma: f32,
+}
+
+#[derive(Clone, Default, Bundle, LdtkEntity)]
+pub struct CameraZoneBundle {
+    #[from_entity_instance]
+    camera_zone: CameraZone,
+}
+
+// While the player is inside, the camera zooms to the zone's scale and either frames its focus point
+// or sticks to its center on the locked axes
+#[derive(Clone, Component, Default)]
+pub struct CameraZone {
+    half_size: Vec2,
+    zoom: f32,
+    lock_x: bool,
+    lock_y: bool,
+    // Relative to the zone's center
+    focus: Option<Vec2>,
+}
+
+impl From<&EntityInstance> for CameraZone {
+    fn from(entity_instance: &EntityInstance) -> Self {
+        // The point is a level cell, make it relative to the zone and flip LDtk's y axis
+        let focus = entity_instance.get_point_field("Focus").ok().map(|cell| {
+            let offset = *cell * ENTITY_GRID_SIZE + IVec2::splat(ENTITY_GRID_SIZE / 2) - entity_instance.px;
+            Vec2::new(offset.x as f32, -offset.y as f32)
+        });
+        CameraZone {
+            half_size: Vec2::new(entity_instance.width as f32, entity_instance.height as f32) / 2.0,
+            zoom: *entity_instance.get_float_field("Zoom").unwrap_or(&DEFAULT_ZOOM),
+            lock_x: *entity_instance.get_bool_field("LockX").unwrap_or(&false),
+            lock_y: *entity_instance.get_bool_field("LockY").unwrap_or(&false),
+            focus,
+        }
+    }
+}
+
+// Send to shake the camera, trauma adds up and is capped at 1
+#[derive(Event)]
+pub struct CameraShake {
+    pub trauma: f32,
+}
+
+// Fraction of the way to move towards a target this frame for a given per second rate
+fn smoothing(rate: f32, delta_seconds: f32) -> f32 {
+    1.0 - (-rate * delta_seconds).exp()
+}
+
+// Two sines at unrelated frequencies, smooth but irregular enough to read as a shake. Roughly -1 to 1
+fn shake_noise(t: f32, seed: f32) -> f32 {
+    (t + seed).sin() * 0.6 + ((t + seed) * 2.3).sin() * 0.4
+}
+
+// World space rectangle of a level, LDtk's y axis points down
+fn level_rect(level: &bevy_ecs_ldtk::ldtk::Level) -> Rect {
+    Rect::new(
+        level.world_x as f32,
+        -(level.world_y + level.px_hei) as f32,
+        (level.world_x + level.px_wid) as f32,
+        -level.world_y as f32,
+    )
+}
+
+fn update_camera_bounds(
+    time: Res<Time>,
+    level_selection: Res<LevelSelection>,
+    ldtk_projects: Query<&Handle<LdtkProject>>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    mut bounds: ResMut<CameraBounds>,
+) {
+    let project = if let Some(project) = ldtk_projects.get_single().ok().and_then(|handle| ldtk_project_assets.get(handle)) {
+        project
+    } else {
+        return;
+    };
+    let level = match &*level_selection {
+        LevelSelection::Iid(iid) => project.get_raw_level_by_iid(iid.get()),
+        LevelSelection::Identifier(identifier) => project.json_data().levels.iter().find(|level| level.identifier == *identifier),
+        LevelSelection::Indices(indices) => project.json_data().levels.get(indices.level),
+        _ => None,
+    };
+    let target = if let Some(level) = level {
+        level_rect(level)
+    } else {
+        return;
+    };
+
+    bounds.rect = Some(match bounds.rect {
+        Some(current) => {
+            let blend = smoothing(BOUNDS_BLEND_RATE, time.delta_seconds());
+            Rect {
+                min: current.min.lerp(target.min, blend),
+                max: current.max.lerp(target.max, blend),
+            }
+        }
+        None => target,
+    });
+}
+
+// Keeps the view inside [min, max] on one axis, centering it when the level is smaller than the view
+fn clamp_view(position: f32, min: f32, max: f32, half_view: f32) -> f32 {
+    if max - min <= half_view * 2.0 {
+        (min + max) / 2.0
+    } else {
+        position.clamp(min + half_view, max - half_view)
+    }
+}
+
+fn add_trauma(mut shake_events: EventReader<CameraShake>, mut rig: ResMut<CameraRig>) {
+    for shake in shake_events.read() {
+        rig.trauma = (rig.trauma + shake.trauma).min(1.0);
+    }
+}
+
+pub fn camera_follow_system(
+    time: Res<Time>,
+    mut bounds: ResMut<CameraBounds>,
+    mut rig: ResMut<CameraRig>,
+    player_query: Query<(&Transform, &Velocity), With<Player>>,
+    grapple_query: Query<&HookState, With<Grapple>>,
+    zone_query: Query<(&CameraZone, &GlobalTransform)>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), (With<Camera>, Without<Player>)>,
+) {
+    let (player_transform, velocity) = if let Ok(player) = player_query.get_single() {
+        player
+    } else {
+        return;
+    };
+    let (mut camera_transform, mut projection) = if let Ok(camera) = camera_query.get_single_mut() {
+        camera
+    } else {
+        return;
+    };
+    let delta_seconds = time.delta_seconds();
+    let player_position = player_transform.translation.truncate();
+
+    let mut focus = match rig.focus {
+        Some(focus) if focus.distance(player_position) < SNAP_DISTANCE => focus,
+        _ => {
+            rig.position = player_position;
+            rig.look_ahead = Vec2::ZERO;
+            // Let the bounds jump to the new level too rather than ease over from the old one
+            bounds.rect = None;
+            player_position
+        }
+    };
+    let offset = player_position - focus;
+    focus += offset - offset.clamp(-DEAD_ZONE, DEAD_ZONE);
+    rig.focus = Some(focus);
+
+    let grappling = grapple_query
+        .iter()
+        .any(|state| matches!(state, HookState::Swinging | HookState::Pulling | HookState::Ziplining));
+    let look_ahead_time = if grappling { GRAPPLE_LOOK_AHEAD_TIME } else { LOOK_AHEAD_TIME };
+    let target_look_ahead = Vec2::new(
+        (velocity.linvel.x * look_ahead_time).clamp(-MAX_LOOK_AHEAD, MAX_LOOK_AHEAD),
+        (velocity.linvel.y.min(0.0) * FALL_LOOK_AHEAD_TIME).max(-MAX_FALL_LOOK_AHEAD),
+    );
+    rig.look_ahead = rig.look_ahead.lerp(target_look_ahead, smoothing(LOOK_AHEAD_RATE, delta_seconds));
+
+    let mut target = focus + rig.look_ahead;
+    let mut zoom = DEFAULT_ZOOM;
+    let zone = zone_query.iter().find(|(zone, zone_transform)| {
+        (player_position - zone_transform.translation().truncate()).abs().cmple(zone.half_size).all()
+    });
+    if let Some((zone, zone_transform)) = zone {
+        let center = zone_transform.translation().truncate();
+        zoom = zone.zoom;
+        if let Some(zone_focus) = zone.focus {
+            target = center + zone_focus;
+        } else {
+            if zone.lock_x {
+                target.x = center.x;
+            }
+            if zone.lock_y {
+                target.y = center.y;
+            }
+        }
+    }
+    // Switching targets eases over through the follow below, the zoom eases on its own
+    projection.scale += (zoom - projection.scale) * smoothing(ZOOM_BLEND_RATE, delta_seconds);
+    rig.position = rig.position.lerp(target, smoothing(FOLLOW_RATE, delta_seconds));
+
+    // The projection's area is the visible world rectangle, it already accounts for the window size and zoom
+    if let Some(rect) = bounds.rect {
+        let half_view = projection.area.half_size();
+        rig.position.x = clamp_view(rig.position.x, rect.min.x, rect.max.x, half_view.x);
+        rig.position.y = clamp_view(rig.position.y, rect.min.y, rect.max.y, half_view.y);
+    }
+
+    rig.trauma = (rig.trauma - TRAUMA_DECAY * delta_seconds).max(0.0);
+    let shake = rig.trauma * rig.trauma;
+    let t = time.elapsed_seconds() * SHAKE_FREQUENCY;
+    let shake_offset = Vec2::new(shake_noise(t, 0.0), shake_noise(t, 10.0)) * MAX_SHAKE_OFFSET * shake;
+    camera_transform.translation = (rig.position + shake_offset).extend(camera_transform.translation.z);
+    camera_transform.rotation = Quat::from_rotation_z(shake_noise(t, 20.0) * MAX_SHAKE_ROLL * shake);
+}
+
+// A new game shouldn't pan over from wherever the last one ended
+fn reset_camera(mut bounds: ResMut<CameraBounds>, mut rig: ResMut<CameraRig>) {
+    bounds.rect = None;
+    *rig = CameraRig::default();
+}
+
+pub struct CameraPlugin;
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_ldtk_entity::<CameraZoneBundle>("CameraZone")
+            .init_resource::<CameraBounds>()
+            .init_resource::<CameraRig>()
+            .add_event::<CameraShake>()
+            .add_systems(Update, (
+                update_camera_bounds,
+                add_trauma,
+                camera_follow_system.after(update_camera_bounds).after(add_trauma),
+            ).run_if(in_state(GameState::Game)))
+            .add_systems(OnEnter(GameState::MainMenu), reset_camera);
+    }
+}
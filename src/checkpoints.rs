@@ -0,0 +1,107 @@
+//checkpoints.rs
+use crate::physics::SensorBundle;
+use crate::player::Player;
+use crate::save::SaveGame;
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+#[derive(Clone, Default, Bundle, LdtkEntity)]
+pub struct CheckpointBundle {
+    #[from_entity_instance]
+    checkpoint: Checkpoint,
+    #[with(checkpoint_sprite)]
+    sprite: SpriteBundle,
+    #[from_entity_instance]
+    physics: SensorBundle,
+}
+
+#[derive(Clone, Component, Default)]
+pub struct Checkpoint {
+    iid: String,
+}
+
+impl From<&EntityInstance> for Checkpoint {
+    fn from(entity_instance: &EntityInstance) -> Self {
+        Checkpoint {
+            iid: entity_instance.iid.clone(),
+        }
+    }
+}
+
+// Where the player respawns after dying or restarting, starts out as the player's spawn point
+#[derive(Resource, Clone, Default, Debug)]
+pub struct CurrentCheckpoint {
+    pub iid: Option<String>,
+    pub position: Vec2,
+}
+
+fn checkpoint_sprite(entity_instance: &EntityInstance) -> SpriteBundle {
+    SpriteBundle {
+        sprite: Sprite {
+            color: Color::srgb(0.4, 0.4, 0.5),
+            custom_size: Some(Vec2::new(4.0, entity_instance.height as f32)),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn set_spawn_checkpoint(
+    mut current_checkpoint: ResMut<CurrentCheckpoint>,
+    player_query: Query<&Transform, Added<Player>>,
+) {
+    if current_checkpoint.iid.is_some() {
+        return;
+    }
+    if let Ok(player_transform) = player_query.get_single() {
+        current_checkpoint.position = player_transform.translation.truncate();
+    }
+}
+
+fn activate_checkpoint(
+    rapier_context: Res<RapierContext>,
+    mut current_checkpoint: ResMut<CurrentCheckpoint>,
+    mut save_events: EventWriter<SaveGame>,
+    player_query: Query<Entity, With<Player>>,
+    checkpoint_query: Query<(Entity, &Checkpoint, &GlobalTransform)>,
+) {
+    let player_entity = if let Ok(player) = player_query.get_single() {
+        player
+    } else {
+        return;
+    };
+
+    for (checkpoint_entity, checkpoint, checkpoint_transform) in checkpoint_query.iter() {
+        if current_checkpoint.iid.as_ref() == Some(&checkpoint.iid) {
+            continue;
+        }
+        if rapier_context.intersection_pair(player_entity, checkpoint_entity) == Some(true) {
+            current_checkpoint.iid = Some(checkpoint.iid.clone());
+            current_checkpoint.position = checkpoint_transform.translation().truncate();
+            save_events.send(SaveGame);
+        }
+    }
+}
+
+fn update_checkpoint_sprites(
+    current_checkpoint: Res<CurrentCheckpoint>,
+    mut query: Query<(&Checkpoint, &mut Sprite)>,
+) {
+    for (checkpoint, mut sprite) in query.iter_mut() {
+        sprite.color = if current_checkpoint.iid.as_ref() == Some(&checkpoint.iid) {
+            Color::srgb(0.4, 0.9, 0.3)
+        } else {
+            Color::srgb(0.4, 0.4, 0.5)
+        };
+    }
+}
+
+pub struct CheckpointPlugin;
+impl Plugin for CheckpointPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CurrentCheckpoint>()
+            .add_systems(Update, (set_spawn_checkpoint, activate_checkpoint, update_checkpoint_sprites))
+            .register_ldtk_entity::<CheckpointBundle>("Checkpoint");
+    }
+}
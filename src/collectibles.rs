@@ -2,13 +2,20 @@ use bevy::prelude::*;
 use bevy::sprite::*;
 use bevy_ecs_ldtk::prelude::*;
 use bevy_rapier2d::prelude::*;
+use crate::abilities::{Abilities, Ability};
+use crate::camera::CameraShake;
+use crate::game_menu::GameState;
 use crate::physics::{SensorBundle};
 use crate::player::Player;
-use bevy_kira_audio::{Audio, AudioControl};
+use crate::save::SaveGame;
+use crate::sound::SfxChannel;
+use bevy::utils::HashSet;
+use bevy_kira_audio::{AudioChannel, AudioControl};
 
 
 #[derive(Clone, Bundle, Default, LdtkEntity)]
 pub struct CollectibleBundle{
+    #[from_entity_instance]
     collectible: Collectible,
     #[sprite_sheet_bundle]
     pub sprite_sheet_bundle: LdtkSpriteSheetBundle,
@@ -19,7 +26,48 @@ pub struct CollectibleBundle{
 }
 
 #[derive(Clone, Component, Default)]
-pub struct Collectible{}
+pub struct Collectible{
+    pub iid: String,
+    pub ability: Option<Ability>,
+    pub unlock_text: String,
+    pub ends_trial: bool,
+}
+
+//reads the unlock from the collectible's LDtk fields, so designers can reorder unlocks in the map
+impl From<&EntityInstance> for Collectible {
+    fn from(entity_instance: &EntityInstance) -> Self {
+        let ability = entity_instance
+            .get_maybe_string_field("Ability")
+            .ok()
+            .and_then(|name| name.as_deref())
+            .and_then(|name| {
+                let ability = Ability::from_name(name);
+                if ability.is_none() {
+                    warn!("Unknown ability \"{}\" on collectible {}", name, entity_instance.iid);
+                }
+                ability
+            });
+
+        let unlock_text = entity_instance
+            .get_maybe_string_field("UnlockText")
+            .ok()
+            .and_then(|text| text.clone())
+            .unwrap_or_default();
+
+        Collectible {
+            iid: entity_instance.iid.clone(),
+            ability,
+            unlock_text,
+            ends_trial: entity_instance.get_bool_field("EndsTrial").copied().unwrap_or(false),
+        }
+    }
+}
+
+// LDtk iids of every collectible the player has picked up, so they stay gone when levels respawn
+#[derive(Resource, Default)]
+pub struct CollectedArtifacts {
+    pub iids: HashSet<String>,
+}
 
 #[derive(Clone, Component)]
 pub struct FadeOutText {
@@ -56,44 +104,37 @@ fn collect_collectible(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     rapier_context: Res<RapierContext>,
-    audio: Res<Audio>,
-    mut player_query: Query<(Entity, &mut Player, &mut Transform, &mut Velocity), With<Player>>,
-    collectible_query: Query<Entity, With<Collectible>>,
+    audio: Res<AudioChannel<SfxChannel>>,
+    mut collected_artifacts: ResMut<CollectedArtifacts>,
+    mut save_events: EventWriter<SaveGame>,
+    mut shake_events: EventWriter<CameraShake>,
+    mut player_query: Query<(Entity, &mut Player, &mut Abilities, &mut Transform, &mut Velocity), With<Player>>,
+    collectible_query: Query<(Entity, &Collectible)>,
 ) {
-    let (player_entity, mut player, mut player_transform, mut player_velocity) = if let Ok(player) = player_query.get_single_mut() {
+    let (player_entity, mut player, mut abilities, mut player_transform, mut player_velocity) = if let Ok(player) = player_query.get_single_mut() {
         player
     } else {
         return;
     };
 
-    for collectible_entity in collectible_query.iter() {
+    for (collectible_entity, collectible) in collectible_query.iter() {
         let collected = asset_server.load("sounds/collect.ogg");
         if rapier_context.intersection_pair(player_entity, collectible_entity) == Some(true)
         {
             //print!("collected collectible");
             commands.entity(collectible_entity).despawn();
+            collected_artifacts.iids.insert(collectible.iid.clone());
             player.progression += 1;
             audio.play(collected.clone());
+            shake_events.send(CameraShake { trauma: 0.3 });
 
-            let mut find_text = "";
-
-            match player.progression {
-                4 => {
-                    find_text = "The scientists have deemed you too \n valuable for a test subject. \n\nYou will remain in containment indefinitely \n Trial complete.";
-                }
-                3 => {
-                    find_text = "You unlocked the Grappling Hook!\nPress J to grapple!";
-                }
-                2 => {
-                    find_text = "You unlocked wall climbing!\nHug walls to cling and jump off!";
-                }
-                1 => {
-                    find_text = "You unlocked Double Jump!\nJump midair to gain extra height!";
-                }
-                _ => {}
+            if let Some(ability) = collectible.ability {
+                abilities.grant(ability);
             }
 
-            if (player.progression) <= 3 {
+            let find_text = collectible.unlock_text.as_str();
+
+            if !collectible.ends_trial {
                 print!("player progression: {}", player.progression);
                 commands.entity(player_entity).with_children(|parent| {
                     parent.spawn((
@@ -113,7 +154,8 @@ fn collect_collectible(
                         FadeOutText::default()
                     ));
                 });
-            } else if player.progression >= 4 {
+            } else {
+                player.trial_complete = true;
                 player_transform.translation = Vec3::new(463.0, -1072.0, 10.0);
                 player_velocity.linvel = Vec2::ZERO;
                 commands.spawn(
@@ -132,11 +174,24 @@ fn collect_collectible(
                     }
                 );
             }
+            save_events.send(SaveGame);
         }
     }
 }
 
 
+fn despawn_collected(
+    mut commands: Commands,
+    collected: Res<CollectedArtifacts>,
+    collectible_query: Query<(Entity, &Collectible)>,
+) {
+    for (collectible_entity, collectible) in collectible_query.iter() {
+        if collected.iids.contains(&collectible.iid) {
+            commands.entity(collectible_entity).despawn();
+        }
+    }
+}
+
 fn animate_collectibles(
     time: Res<Time>,
     mut query: Query<&mut Transform, With<Collectible>>,
@@ -151,7 +206,8 @@ fn animate_collectibles(
     pub struct CollectiblePlugin;
     impl Plugin for CollectiblePlugin {
         fn build(&self, app: &mut App) {
-            app.add_systems(Update, (animate_collectibles, collect_collectible, handle_text_fade))
+            app.init_resource::<CollectedArtifacts>()
+                .add_systems(Update, (animate_collectibles, despawn_collected.before(collect_collectible), collect_collectible, handle_text_fade).run_if(in_state(GameState::Game)))
                 .register_ldtk_entity::<CollectibleBundle>("DefaultCollectible");
             //.register_ldtk_entity::<CollectibleBundle>("Boots");
             //.register_ldtk_entity::<CollectibleBundle>("Pills");
@@ -0,0 +1,457 @@
+//controls.rs
+use crate::game_menu::GameState;
+use crate::save::data_dir;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+
+const CONTROLS_FILE_NAME: &str = "controls.ron";
+pub const STICK_DEADZONE: f32 = 0.5;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    Up,
+    Jump,
+    FastFall,
+    Grapple,
+    Restart,
+}
+
+impl Action {
+    pub const ALL: [Action; 7] = [
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Up,
+        Action::Jump,
+        Action::FastFall,
+        Action::Grapple,
+        Action::Restart,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::MoveLeft => "Move Left",
+            Action::MoveRight => "Move Right",
+            Action::Up => "Aim Up",
+            Action::Jump => "Jump",
+            Action::FastFall => "Fast Fall",
+            Action::Grapple => "Grapple",
+            Action::Restart => "Respawn",
+        }
+    }
+}
+
+//keyboard and gamepad bindings for every action, persisted to controls.ron next to the save file
+#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+pub struct InputMap {
+    pub keyboard: HashMap<Action, Vec<KeyCode>>,
+    pub gamepad: HashMap<Action, Vec<GamepadButtonType>>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        let keyboard = HashMap::from([
+            (Action::MoveLeft, vec![KeyCode::KeyA, KeyCode::ArrowLeft]),
+            (Action::MoveRight, vec![KeyCode::KeyD, KeyCode::ArrowRight]),
+            (Action::Up, vec![KeyCode::KeyW, KeyCode::ArrowUp]),
+            (Action::Jump, vec![KeyCode::Space]),
+            (Action::FastFall, vec![KeyCode::KeyS, KeyCode::ArrowDown]),
+            (Action::Grapple, vec![KeyCode::KeyJ, KeyCode::ShiftLeft]),
+            (Action::Restart, vec![KeyCode::KeyR]),
+        ]);
+        let gamepad = HashMap::from([
+            (Action::MoveLeft, vec![GamepadButtonType::DPadLeft]),
+            (Action::MoveRight, vec![GamepadButtonType::DPadRight]),
+            (Action::Up, vec![GamepadButtonType::DPadUp]),
+            (Action::Jump, vec![GamepadButtonType::South]),
+            (Action::FastFall, vec![GamepadButtonType::DPadDown, GamepadButtonType::LeftTrigger2, GamepadButtonType::RightTrigger2]),
+            (Action::Grapple, vec![GamepadButtonType::West, GamepadButtonType::East]),
+            (Action::Restart, vec![GamepadButtonType::Select]),
+        ]);
+        InputMap { keyboard, gamepad }
+    }
+}
+
+impl InputMap {
+    pub fn keys(&self, action: Action) -> &[KeyCode] {
+        self.keyboard.get(&action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn buttons(&self, action: Action) -> &[GamepadButtonType] {
+        self.gamepad.get(&action).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+// Everything needed to ask "is this action pressed" across keyboard and all connected gamepads
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    input_map: Res<'w, InputMap>,
+    keyboard: Res<'w, ButtonInput<KeyCode>>,
+    gamepads: Res<'w, Gamepads>,
+    gamepad_buttons: Res<'w, ButtonInput<GamepadButton>>,
+    gamepad_axes: Res<'w, Axis<GamepadAxis>>,
+}
+
+impl ActionInput<'_> {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.keyboard.any_pressed(self.input_map.keys(action).iter().copied())
+            || self.gamepads.iter().any(|gamepad| {
+                self.gamepad_buttons.any_pressed(
+                    self.input_map.buttons(action).iter().map(|button| GamepadButton::new(gamepad, *button)),
+                )
+            })
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.keyboard.any_just_pressed(self.input_map.keys(action).iter().copied())
+            || self.gamepads.iter().any(|gamepad| {
+                self.gamepad_buttons.any_just_pressed(
+                    self.input_map.buttons(action).iter().map(|button| GamepadButton::new(gamepad, *button)),
+                )
+            })
+    }
+
+    pub fn left_stick(&self) -> Vec2 {
+        self.stick(GamepadAxisType::LeftStickX, GamepadAxisType::LeftStickY)
+    }
+
+    pub fn right_stick(&self) -> Vec2 {
+        self.stick(GamepadAxisType::RightStickX, GamepadAxisType::RightStickY)
+    }
+
+    // Stick of whichever gamepad is pushed furthest, zero if none is past the deadzone
+    fn stick(&self, x_axis: GamepadAxisType, y_axis: GamepadAxisType) -> Vec2 {
+        self.gamepads
+            .iter()
+            .map(|gamepad| {
+                Vec2::new(
+                    self.gamepad_axes.get(GamepadAxis::new(gamepad, x_axis)).unwrap_or(0.0),
+                    self.gamepad_axes.get(GamepadAxis::new(gamepad, y_axis)).unwrap_or(0.0),
+                )
+            })
+            .filter(|stick| stick.length() > STICK_DEADZONE)
+            .max_by(|a, b| a.length().total_cmp(&b.length()))
+            .unwrap_or(Vec2::ZERO)
+    }
+}
+
+fn load_input_map() -> InputMap {
+    let mut input_map: InputMap = fs::read_to_string(data_dir().join(CONTROLS_FILE_NAME))
+        .ok()
+        .and_then(|contents| match ron::from_str(&contents) {
+            Ok(input_map) => Some(input_map),
+            Err(err) => {
+                warn!("Could not read controls file, using defaults: {}", err);
+                None
+            }
+        })
+        .unwrap_or_default();
+
+    // Actions added since the file was written fall back to their default bindings
+    let defaults = InputMap::default();
+    for action in Action::ALL {
+        input_map.keyboard.entry(action).or_insert_with(|| defaults.keys(action).to_vec());
+        input_map.gamepad.entry(action).or_insert_with(|| defaults.buttons(action).to_vec());
+    }
+    input_map
+}
+
+fn write_input_map(input_map: &InputMap) {
+    let dir = data_dir();
+    if let Err(err) = fs::create_dir_all(&dir) {
+        warn!("Could not create config directory {:?}: {}", dir, err);
+        return;
+    }
+    match ron::ser::to_string_pretty(input_map, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => {
+            if let Err(err) = fs::write(dir.join(CONTROLS_FILE_NAME), contents) {
+                warn!("Could not write controls file: {}", err);
+            }
+        }
+        Err(err) => warn!("Could not serialize controls: {}", err),
+    }
+}
+
+//controls screen
+
+#[derive(Component)]
+pub struct ControlsPopup;
+
+#[derive(Component)]
+pub struct RebindButton(Action);
+
+#[derive(Component)]
+pub struct BindingText(Action);
+
+#[derive(Component)]
+pub struct ResetControlsButton;
+
+#[derive(Component)]
+pub struct CloseControlsButton;
+
+// The action waiting for its next key or gamepad button, if any
+#[derive(Resource, Default)]
+pub struct Rebinding(Option<Action>);
+
+fn key_name(key: &KeyCode) -> String {
+    let name = format!("{:?}", key);
+    name.strip_prefix("Key").map(str::to_string).unwrap_or(name)
+}
+
+fn binding_label(input_map: &InputMap, action: Action) -> String {
+    let keys: Vec<String> = input_map.keys(action).iter().map(key_name).collect();
+    let buttons: Vec<String> = input_map.buttons(action).iter().map(|button| format!("{:?}", button)).collect();
+    format!("{}  |  {}", keys.join(" / "), buttons.join(" / "))
+}
+
+pub fn show_controls_popup(commands: &mut Commands, asset_server: &AssetServer, input_map: &InputMap) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+
+    commands.spawn((
+        // Spawn a container for both the background and the popup
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.7).into(), // Dimmed background
+            ..Default::default()
+        },
+        ControlsPopup,
+    ))
+        .with_children(|parent| {
+            // Popup container
+            parent.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(700.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(20.0)),
+                    flex_direction: FlexDirection::Column,
+                    ..Default::default()
+                },
+                background_color: Color::srgb(0.2, 0.2, 0.3).into(), // Dark blue-gray
+                ..Default::default()
+            })
+                .with_children(|popup| {
+                    popup.spawn(TextBundle::from_section(
+                        "Controls - click a binding, then press a key or gamepad button",
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 22.0,
+                            color: Color::WHITE,
+                        },
+                    ).with_style(Style {
+                        margin: UiRect::all(Val::Px(10.0)),
+                        ..Default::default()
+                    }));
+
+                    for action in Action::ALL {
+                        popup.spawn(NodeBundle {
+                            style: Style {
+                                width: Val::Percent(100.0),
+                                justify_content: JustifyContent::SpaceBetween,
+                                align_items: AlignItems::Center,
+                                margin: UiRect::all(Val::Px(4.0)),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        })
+                            .with_children(|row| {
+                                row.spawn(TextBundle::from_section(
+                                    action.label(),
+                                    TextStyle {
+                                        font: font.clone(),
+                                        font_size: 20.0,
+                                        color: Color::WHITE,
+                                    },
+                                ));
+                                row.spawn((
+                                    ButtonBundle {
+                                        style: Style {
+                                            width: Val::Px(460.0),
+                                            height: Val::Px(36.0),
+                                            justify_content: JustifyContent::Center,
+                                            align_items: AlignItems::Center,
+                                            ..Default::default()
+                                        },
+                                        background_color: Color::srgb(0.9, 0.9, 0.9).into(),
+                                        ..Default::default()
+                                    },
+                                    RebindButton(action),
+                                ))
+                                    .with_children(|button| {
+                                        button.spawn((
+                                            TextBundle::from_section(
+                                                binding_label(input_map, action),
+                                                TextStyle {
+                                                    font: font.clone(),
+                                                    font_size: 18.0,
+                                                    color: Color::BLACK,
+                                                },
+                                            ),
+                                            BindingText(action),
+                                        ));
+                                    });
+                            });
+                    }
+
+                    popup.spawn(NodeBundle {
+                        style: Style {
+                            margin: UiRect::top(Val::Px(10.0)),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                        .with_children(|row| {
+                            row.spawn((
+                                ButtonBundle {
+                                    style: Style {
+                                        width: Val::Px(160.0),
+                                        height: Val::Px(40.0),
+                                        margin: UiRect::all(Val::Px(10.0)),
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..Default::default()
+                                    },
+                                    background_color: Color::srgb(0.3, 0.3, 0.4).into(),
+                                    ..Default::default()
+                                },
+                                ResetControlsButton,
+                            ))
+                                .with_children(|button| {
+                                    button.spawn(TextBundle::from_section(
+                                        "Reset Defaults",
+                                        TextStyle {
+                                            font: font.clone(),
+                                            font_size: 20.0,
+                                            color: Color::WHITE,
+                                        },
+                                    ));
+                                });
+
+                            // Close button
+                            row.spawn((
+                                ButtonBundle {
+                                    style: Style {
+                                        width: Val::Px(120.0),
+                                        height: Val::Px(40.0),
+                                        margin: UiRect::all(Val::Px(10.0)),
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..Default::default()
+                                    },
+                                    background_color: Color::srgb(0.8, 0.0, 0.0).into(), // Bright red
+                                    ..Default::default()
+                                },
+                                CloseControlsButton,
+                            ))
+                                .with_children(|button| {
+                                    button.spawn(TextBundle::from_section(
+                                        "Close",
+                                        TextStyle {
+                                            font: font.clone(),
+                                            font_size: 20.0,
+                                            color: Color::WHITE,
+                                        },
+                                    ));
+                                });
+                        });
+                });
+        });
+}
+
+fn controls_popup_interaction(
+    mut commands: Commands,
+    mut rebinding: ResMut<Rebinding>,
+    mut input_map: ResMut<InputMap>,
+    mut rebind_query: Query<(&Interaction, &RebindButton, &mut BackgroundColor), Changed<Interaction>>,
+    reset_query: Query<&Interaction, (Changed<Interaction>, With<ResetControlsButton>)>,
+    close_query: Query<&Interaction, (Changed<Interaction>, With<CloseControlsButton>)>,
+    popup_query: Query<Entity, With<ControlsPopup>>,
+) {
+    for (interaction, rebind_button, mut color) in &mut rebind_query {
+        match *interaction {
+            Interaction::Pressed => rebinding.0 = Some(rebind_button.0),
+            Interaction::Hovered => *color = Color::srgb(0.7, 0.7, 0.7).into(),
+            Interaction::None => *color = Color::srgb(0.9, 0.9, 0.9).into(),
+        }
+    }
+
+    if reset_query.iter().any(|interaction| *interaction == Interaction::Pressed) {
+        *input_map = InputMap::default();
+        rebinding.0 = None;
+        write_input_map(&input_map);
+    }
+
+    if close_query.iter().any(|interaction| *interaction == Interaction::Pressed) {
+        rebinding.0 = None;
+        for popup_entity in popup_query.iter() {
+            commands.entity(popup_entity).despawn_recursive();
+        }
+    }
+}
+
+// Binds the next key or gamepad button pressed to the action being rebound, Esc cancels
+fn capture_rebind(
+    mut rebinding: ResMut<Rebinding>,
+    mut input_map: ResMut<InputMap>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+) {
+    let action = if let Some(action) = rebinding.0 {
+        action
+    } else {
+        return;
+    };
+
+    if let Some(key) = keyboard_input.get_just_pressed().next() {
+        if *key != KeyCode::Escape {
+            input_map.keyboard.insert(action, vec![*key]);
+            write_input_map(&input_map);
+        }
+        rebinding.0 = None;
+    } else if let Some(button) = gamepad_buttons.get_just_pressed().next() {
+        input_map.gamepad.insert(action, vec![button.button_type]);
+        write_input_map(&input_map);
+        rebinding.0 = None;
+    }
+}
+
+fn update_binding_texts(
+    input_map: Res<InputMap>,
+    rebinding: Res<Rebinding>,
+    mut query: Query<(&mut Text, &BindingText)>,
+) {
+    if !input_map.is_changed() && !rebinding.is_changed() {
+        return;
+    }
+    for (mut text, binding) in query.iter_mut() {
+        text.sections[0].value = if rebinding.0 == Some(binding.0) {
+            "Press a key or button... (Esc to cancel)".to_string()
+        } else {
+            binding_label(&input_map, binding.0)
+        };
+    }
+}
+
+pub struct ControlsPlugin;
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_input_map())
+            .init_resource::<Rebinding>()
+            .add_systems(Update, (
+                controls_popup_interaction,
+                capture_rebind.after(controls_popup_interaction),
+                update_binding_texts.after(capture_rebind),
+            ).run_if(in_state(GameState::MainMenu)));
+    }
+}
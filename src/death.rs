@@ -0,0 +1,142 @@
+//death.rs
+use crate::checkpoints::CurrentCheckpoint;
+use crate::game_menu::GameState;
+use crate::grapple::Grapple;
+use crate::player::{reset_position, Player};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+// The death animation plays, the screen fades to black, the player respawns at the checkpoint and it fades back in.
+// Input stays locked for the whole sequence
+const DEATH_ANIMATION_TIME: f32 = 0.6;
+const DEATH_FADE_TIME: f32 = 0.3;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DeathCause {
+    Fall,
+    Lava,
+}
+
+#[derive(Event)]
+pub struct PlayerDied {
+    pub cause: DeathCause,
+}
+
+// On the player from death until it has respawned and the screen has faded back in
+#[derive(Component)]
+pub struct Dying {
+    timer: Timer,
+    respawned: bool,
+}
+
+#[derive(Resource, Default)]
+pub struct DeathCounter {
+    pub deaths: u32,
+}
+
+#[derive(Component)]
+struct DeathFade;
+
+fn setup_death_fade(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                ..Default::default()
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.0).into(),
+            z_index: ZIndex::Global(100),
+            ..Default::default()
+        },
+        DeathFade,
+    ));
+}
+
+fn handle_player_died(
+    mut commands: Commands,
+    mut death_events: EventReader<PlayerDied>,
+    mut death_counter: ResMut<DeathCounter>,
+    grapple_query: Query<Entity, With<Grapple>>,
+    mut player_query: Query<(Entity, &mut Velocity, &mut LockedAxes), (With<Player>, Without<Dying>)>,
+) {
+    // Several causes can land on the same frame, it's still one death
+    let cause = if let Some(event) = death_events.read().last() {
+        event.cause
+    } else {
+        return;
+    };
+    let (player_entity, mut velocity, mut locked_axes) = if let Ok(player) = player_query.get_single_mut() {
+        player
+    } else {
+        return;
+    };
+
+    death_counter.deaths += 1;
+    info!("Player died ({:?}), deaths: {}", cause, death_counter.deaths);
+
+    for grapple_entity in grapple_query.iter() {
+        commands.entity(grapple_entity).despawn_recursive();
+    }
+    velocity.linvel = Vec2::ZERO;
+    *locked_axes = LockedAxes::ROTATION_LOCKED | LockedAxes::TRANSLATION_LOCKED;
+    commands.entity(player_entity).remove::<ImpulseJoint>().insert(Dying {
+        timer: Timer::from_seconds(DEATH_ANIMATION_TIME + 2.0 * DEATH_FADE_TIME, TimerMode::Once),
+        respawned: false,
+    });
+}
+
+fn update_dying(
+    mut commands: Commands,
+    time: Res<Time>,
+    checkpoint: Res<CurrentCheckpoint>,
+    mut player_query: Query<(Entity, &mut Dying, &mut Transform, &mut Velocity, &mut LockedAxes), With<Player>>,
+    mut fade_query: Query<&mut BackgroundColor, With<DeathFade>>,
+) {
+    for (player_entity, mut dying, mut transform, mut velocity, mut locked_axes) in player_query.iter_mut() {
+        dying.timer.tick(time.delta());
+        let elapsed = dying.timer.elapsed_secs();
+
+        if !dying.respawned && elapsed >= DEATH_ANIMATION_TIME + DEATH_FADE_TIME {
+            // Hidden behind the fade
+            *transform = reset_position(transform.clone(), &checkpoint);
+            velocity.linvel = Vec2::ZERO;
+            dying.respawned = true;
+        }
+
+        let fade = if elapsed < DEATH_ANIMATION_TIME {
+            0.0
+        } else if !dying.respawned {
+            (elapsed - DEATH_ANIMATION_TIME) / DEATH_FADE_TIME
+        } else {
+            1.0 - (elapsed - DEATH_ANIMATION_TIME - DEATH_FADE_TIME) / DEATH_FADE_TIME
+        };
+        for mut color in fade_query.iter_mut() {
+            *color = Color::srgba(0.0, 0.0, 0.0, fade.clamp(0.0, 1.0)).into();
+        }
+
+        if dying.timer.finished() {
+            *locked_axes = LockedAxes::ROTATION_LOCKED;
+            commands.entity(player_entity).remove::<Dying>();
+        }
+    }
+}
+
+// Leaving the game mid-fade shouldn't leave the screen black
+fn clear_death_fade(mut fade_query: Query<&mut BackgroundColor, With<DeathFade>>) {
+    for mut color in fade_query.iter_mut() {
+        *color = Color::srgba(0.0, 0.0, 0.0, 0.0).into();
+    }
+}
+
+pub struct DeathPlugin;
+impl Plugin for DeathPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PlayerDied>()
+            .init_resource::<DeathCounter>()
+            .add_systems(Startup, setup_death_fade)
+            .add_systems(Update, (handle_player_died, update_dying.after(handle_player_died)).run_if(in_state(GameState::Game)))
+            .add_systems(OnEnter(GameState::MainMenu), clear_death_fade);
+    }
+}
@@ -0,0 +1,313 @@
+//debug.rs
+use crate::abilities::{Abilities, Ability};
+use crate::grapple::{Grapple, HookState};
+use crate::ground_detection::GroundDetection;
+use crate::player::Player;
+use crate::wall_climb::ClimbDetection;
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+const CONSOLE_HELP: &str = "commands: progression <n>, ability <DoubleJump|WallClimb|Grapple> on|off, tp <level index or name>, colliders, help";
+
+// F3 overlay with live player, grapple and level state
+#[derive(Resource, Default)]
+pub struct DebugOverlay {
+    pub visible: bool,
+}
+
+// Backquote opens a one-line command console, gameplay input is ignored while it's open
+#[derive(Resource, Default)]
+pub struct DebugConsole {
+    pub open: bool,
+    input: String,
+    output: String,
+}
+
+#[derive(Event)]
+pub struct ConsoleCommand(pub String);
+
+#[derive(Component)]
+struct DebugOverlayText;
+
+#[derive(Component)]
+struct DebugConsoleRoot;
+
+#[derive(Component)]
+struct DebugConsoleText;
+
+pub fn console_closed(console: Res<DebugConsole>) -> bool {
+    !console.open
+}
+
+fn setup_debug_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: font.clone(),
+                font_size: 18.0,
+                color: Color::srgb(0.8, 1.0, 0.8),
+            },
+        )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                right: Val::Px(10.0),
+                display: Display::None,
+                ..Default::default()
+            })
+            .with_background_color(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        DebugOverlayText,
+    ));
+
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(0.0),
+                left: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                display: Display::None,
+                ..Default::default()
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.8).into(),
+            ..Default::default()
+        },
+        DebugConsoleRoot,
+    ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font,
+                        font_size: 20.0,
+                        color: Color::WHITE,
+                    },
+                ),
+                DebugConsoleText,
+            ));
+        });
+}
+
+fn toggle_debug_ui(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<DebugOverlay>,
+    mut console: ResMut<DebugConsole>,
+    mut overlay_query: Query<&mut Style, (With<DebugOverlayText>, Without<DebugConsoleRoot>)>,
+    mut console_query: Query<&mut Style, (With<DebugConsoleRoot>, Without<DebugOverlayText>)>,
+) {
+    if keyboard_input.just_pressed(KeyCode::F3) {
+        overlay.visible = !overlay.visible;
+        for mut style in overlay_query.iter_mut() {
+            style.display = if overlay.visible { Display::Flex } else { Display::None };
+        }
+    }
+    if keyboard_input.just_pressed(KeyCode::Backquote) {
+        console.open = !console.open;
+        console.input.clear();
+        for mut style in console_query.iter_mut() {
+            style.display = if console.open { Display::Flex } else { Display::None };
+        }
+    }
+}
+
+// Edits the console line from typed characters, Enter sends it as a command
+fn console_input(
+    mut console: ResMut<DebugConsole>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut commands: EventWriter<ConsoleCommand>,
+) {
+    for event in keyboard_events.read() {
+        if !console.open || event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Enter => {
+                let line = std::mem::take(&mut console.input);
+                if !line.trim().is_empty() {
+                    commands.send(ConsoleCommand(line));
+                }
+            }
+            Key::Backspace => {
+                console.input.pop();
+            }
+            Key::Space => console.input.push(' '),
+            // The backquote that opened the console isn't part of the command
+            Key::Character(text) if text.as_str() != "`" => console.input.push_str(text),
+            _ => {}
+        }
+    }
+}
+
+fn run_console_command(
+    mut commands: EventReader<ConsoleCommand>,
+    mut console: ResMut<DebugConsole>,
+    mut level_selection: ResMut<LevelSelection>,
+    mut debug_render: ResMut<DebugRenderContext>,
+    ldtk_projects: Query<&Handle<LdtkProject>>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    mut player_query: Query<(&mut Player, &mut Abilities, &mut Transform, &mut Velocity)>,
+) {
+    for ConsoleCommand(line) in commands.read() {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or_default();
+        let argument = words.next();
+        let value = words.next();
+
+        console.output = match (command, argument) {
+            ("help", _) => CONSOLE_HELP.to_string(),
+            ("colliders", _) => {
+                debug_render.enabled = !debug_render.enabled;
+                format!("collider rendering {}", if debug_render.enabled { "on" } else { "off" })
+            }
+            ("progression", Some(value)) => match (value.parse::<u8>(), player_query.get_single_mut()) {
+                (Ok(progression), Ok((mut player, _, _, _))) => {
+                    player.progression = progression;
+                    format!("progression set to {}", progression)
+                }
+                (Err(_), _) => format!("not a progression value: {}", value),
+                (_, Err(_)) => "no player spawned".to_string(),
+            },
+            ("ability", Some(name)) => match (Ability::from_name(name), value, player_query.get_single_mut()) {
+                (Some(ability), Some("on"), Ok((_, mut abilities, _, _))) => {
+                    abilities.grant(ability);
+                    format!("{:?} granted", ability)
+                }
+                (Some(ability), Some("off"), Ok((_, mut abilities, _, _))) => {
+                    abilities.revoke(ability);
+                    format!("{:?} revoked", ability)
+                }
+                (None, _, _) => format!("not an ability: {}", name),
+                (_, _, Err(_)) => "no player spawned".to_string(),
+                (_, _, _) => "usage: ability <name> on|off".to_string(),
+            },
+            ("tp", Some(target)) => {
+                let project = ldtk_projects.get_single().ok().and_then(|handle| ldtk_project_assets.get(handle));
+                match (project, player_query.get_single_mut()) {
+                    (Some(project), Ok((_, _, mut transform, mut velocity))) => match find_level(project, target) {
+                        Some((identifier, position)) => {
+                            // Select the level too, it may be too far away to be loaded yet
+                            *level_selection = LevelSelection::Identifier(identifier.clone());
+                            transform.translation = position.extend(transform.translation.z);
+                            velocity.linvel = Vec2::ZERO;
+                            format!("teleported to {}", identifier)
+                        }
+                        None => format!("no level called {}", target),
+                    },
+                    _ => "no world loaded".to_string(),
+                }
+            }
+            _ => format!("unknown command: {} ({})", line.trim(), CONSOLE_HELP),
+        };
+    }
+}
+
+// Level identifier and a safe spot in it: its first checkpoint, or the middle of the level
+fn find_level(project: &LdtkProject, target: &str) -> Option<(String, Vec2)> {
+    let levels = &project.json_data().levels;
+    let level = match target.parse::<usize>() {
+        Ok(index) => levels.get(index),
+        Err(_) => levels.iter().find(|level| level.identifier.eq_ignore_ascii_case(target)),
+    }?;
+
+    // LDtk's y axis points down
+    let origin = Vec2::new(level.world_x as f32, -level.world_y as f32);
+    let checkpoint = level
+        .layer_instances
+        .iter()
+        .flatten()
+        .flat_map(|layer| layer.entity_instances.iter())
+        .find(|entity| entity.identifier == "Checkpoint")
+        .map(|entity| origin + Vec2::new(entity.px.x as f32, -entity.px.y as f32));
+    let center = origin + Vec2::new(level.px_wid as f32, -level.px_hei as f32) / 2.0;
+    Some((level.identifier.clone(), checkpoint.unwrap_or(center)))
+}
+
+fn update_debug_overlay(
+    overlay: Res<DebugOverlay>,
+    diagnostics: Res<DiagnosticsStore>,
+    level_selection: Res<LevelSelection>,
+    ldtk_projects: Query<&Handle<LdtkProject>>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    player_query: Query<(&Player, &Abilities, &Velocity, &GroundDetection, &ClimbDetection)>,
+    grapple_query: Query<&HookState, With<Grapple>>,
+    mut text_query: Query<&mut Text, With<DebugOverlayText>>,
+) {
+    if !overlay.visible {
+        return;
+    }
+
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or_default();
+    let level = match &*level_selection {
+        LevelSelection::Iid(iid) => ldtk_projects
+            .get_single()
+            .ok()
+            .and_then(|handle| ldtk_project_assets.get(handle))
+            .and_then(|project| project.get_raw_level_by_iid(iid.get()))
+            .map(|level| level.identifier.clone())
+            .unwrap_or_else(|| iid.to_string()),
+        other => format!("{:?}", other),
+    };
+    let hook = grapple_query
+        .get_single()
+        .map(|state| format!("{:?}", state))
+        .unwrap_or_else(|_| "none".to_string());
+
+    let mut lines = vec![format!("FPS: {:.0}", fps), format!("Level: {}", level)];
+    if let Ok((player, abilities, velocity, ground_detection, climb_detection)) = player_query.get_single() {
+        lines.push(format!("Velocity: ({:.0}, {:.0})", velocity.linvel.x, velocity.linvel.y));
+        lines.push(format!("On ground: {}", ground_detection.on_ground));
+        lines.push(format!("Wall: {:?}", climb_detection.side));
+        lines.push(format!("Progression: {}", player.progression));
+        lines.push(format!("Abilities: {:?}", abilities.iter().collect::<Vec<_>>()));
+    }
+    lines.push(format!("Hook: {}", hook));
+
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = lines.join("\n");
+    }
+}
+
+fn update_console_text(console: Res<DebugConsole>, mut query: Query<&mut Text, With<DebugConsoleText>>) {
+    if !console.is_changed() {
+        return;
+    }
+    for mut text in query.iter_mut() {
+        text.sections[0].value = format!("{}\n> {}_", console.output, console.input);
+    }
+}
+
+pub struct DebugPlugin;
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin);
+        }
+        app.add_plugins(RapierDebugRenderPlugin::default().disabled())
+            .init_resource::<DebugOverlay>()
+            .insert_resource(DebugConsole {
+                output: CONSOLE_HELP.to_string(),
+                ..Default::default()
+            })
+            .add_event::<ConsoleCommand>()
+            .add_systems(Startup, setup_debug_ui)
+            .add_systems(Update, (
+                toggle_debug_ui,
+                console_input.after(toggle_debug_ui),
+                run_console_command.after(console_input),
+                update_debug_overlay,
+                update_console_text,
+            ));
+    }
+}
@@ -1,15 +1,27 @@
 use bevy_ecs_ldtk::LevelSelection;
 use bevy::prelude::*;
 use bevy_ecs_ldtk::LdtkWorldBundle;
-use bevy_kira_audio::{Audio, AudioControl};
+use bevy_kira_audio::{AudioChannel, AudioControl};
+use crate::controls::{show_controls_popup, InputMap};
+use crate::save::{load_save, save_exists, PendingSave};
+use crate::sound::{show_settings_popup, AudioSettings, UiChannel};
 
 
 #[derive(Component)]
 pub struct StartButton;
 
+#[derive(Component)]
+pub struct ContinueButton;
+
 #[derive(Component)]
 pub struct RulesButton;
 
+#[derive(Component)]
+pub struct ControlsButton;
+
+#[derive(Component)]
+pub struct SettingsButton;
+
 #[derive(Component)]
 pub struct MenuElement; // Add this component to identify menu elements
 
@@ -27,6 +39,7 @@ pub enum GameState {
     MainMenu,
     Loading,
     Game,
+    Paused,
 }
 
 
@@ -91,6 +104,36 @@ pub fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                     ));
                 });
 
+            // Only offer to continue if there is a save to continue from
+            if save_exists() {
+                parent.spawn((
+                    ButtonBundle {
+                        style: Style {
+                            width: Val::Px(200.0),
+                            height: Val::Px(80.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            margin: UiRect::top(Val::Px(10.0)),
+                            ..Default::default()
+                        },
+                        background_color: Color::srgb(0.9, 0.9, 0.9).into(),
+                        ..Default::default()
+                    },
+                    ContinueButton,
+                    MenuElement,
+                ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            "Continue",
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: 40.0,
+                                color: Color::BLACK,
+                            },
+                        ));
+                    });
+            }
+
             parent.spawn((
                 ButtonBundle {
                     style: Style {
@@ -110,6 +153,59 @@ pub fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                 .with_children(|parent| {
                     parent.spawn(TextBundle::from_section(
                         "About",
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 40.0,
+                            color: Color::BLACK,
+                        },
+                    ));
+                });
+
+            parent.spawn((
+                ButtonBundle {
+                    style: Style {
+                        width: Val::Px(200.0),
+                        height: Val::Px(80.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    background_color: Color::srgb(0.9, 0.9, 0.9).into(),
+                    ..Default::default()
+                },
+                ControlsButton,
+                MenuElement,
+            ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Controls",
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 40.0,
+                            color: Color::BLACK,
+                        },
+                    ));
+                });
+
+            parent.spawn((
+                ButtonBundle {
+                    style: Style {
+                        width: Val::Px(200.0),
+                        height: Val::Px(80.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        margin: UiRect::top(Val::Px(10.0)),
+                        ..Default::default()
+                    },
+                    background_color: Color::srgb(0.9, 0.9, 0.9).into(),
+                    ..Default::default()
+                },
+                SettingsButton,
+                MenuElement,
+            ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Settings",
                         TextStyle {
                             font,
                             font_size: 40.0,
@@ -161,6 +257,50 @@ pub fn rules_button_interaction(
     }
 }
 
+pub fn controls_button_interaction(
+    mut interaction_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<ControlsButton>)>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    input_map: Res<InputMap>,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                info!("Controls button pressed!");
+                show_controls_popup(&mut commands, &asset_server, &input_map);
+            }
+            Interaction::Hovered => {
+                *color = Color::srgb(0.7, 0.7, 0.7).into();
+            }
+            Interaction::None => {
+                *color = Color::srgb(0.9, 0.9, 0.9).into();
+            }
+        }
+    }
+}
+
+pub fn settings_button_interaction(
+    mut interaction_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<SettingsButton>)>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    audio_settings: Res<AudioSettings>,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                info!("Settings button pressed!");
+                show_settings_popup(&mut commands, &asset_server, &audio_settings);
+            }
+            Interaction::Hovered => {
+                *color = Color::srgb(0.7, 0.7, 0.7).into();
+            }
+            Interaction::None => {
+                *color = Color::srgb(0.9, 0.9, 0.9).into();
+            }
+        }
+    }
+}
+
 pub fn show_rules_popup(commands: &mut Commands, asset_server: &AssetServer) {
     let font = asset_server.load("fonts/FiraSans-Bold.ttf");
 
@@ -242,7 +382,7 @@ pub fn show_rules_popup(commands: &mut Commands, asset_server: &AssetServer) {
 
 // System to handle button interaction
 pub fn button_interaction(
-    mut interaction_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<StartButton>)> , audio: Res<Audio>, asset_server: Res<AssetServer>,
+    mut interaction_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<StartButton>)> , audio: Res<AudioChannel<UiChannel>>, asset_server: Res<AssetServer>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
     let start = asset_server.load("sounds/startEffect.ogg");
@@ -266,6 +406,34 @@ pub fn button_interaction(
 
 
 
+pub fn continue_button_interaction(
+    mut interaction_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<ContinueButton>)>,
+    mut commands: Commands,
+    audio: Res<AudioChannel<UiChannel>>,
+    asset_server: Res<AssetServer>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                if let Some(save) = load_save() {
+                    audio.play(asset_server.load("sounds/startEffect.ogg"));
+                    info!("Continue button pressed!");
+                    commands.insert_resource(PendingSave(save));
+                    next_state.set(GameState::Loading);
+                }
+            }
+            Interaction::Hovered => {
+                *color = Color::srgb(0.7, 0.7, 0.7).into();
+            }
+            Interaction::None => {
+                *color = Color::srgb(0.9, 0.9, 0.9).into();
+            }
+        }
+    }
+}
+
+
 // Add a new system to handle the loading transition
 pub fn handle_loading(
     mut commands: Commands,
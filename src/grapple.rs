@@ -1,8 +1,12 @@
 //grapple.rs
+use crate::abilities::{Abilities, Ability};
+use crate::game_menu::GameState;
 use crate::ground_detection::GroundDetection;
 use crate::player::{Player, PlayerInput};
+use crate::tuning::MovementTuning;
 use crate::wall_climb::ClimbDetection;
 use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
 use bevy_rapier2d::prelude::*;
 use std::default::Default;
 
@@ -23,6 +27,135 @@ pub struct GrappleBundle {
 #[derive(Copy, Clone, Default, Debug, Component)]
 pub struct Grapple {
     flying_direction: f32,
+    aim: Vec2,
+    rope_length: f32,
+    // Zipline the hook is riding
+    anchor: Option<Entity>,
+}
+
+// Colliders the hook can latch on to: "hookwall" tiles and GrapplePoint entities.
+// Hitting any other terrain bounces the hook back
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component)]
+pub struct Hookable;
+
+// What happens when the hook latches on a grapple point, set by its "Pull" field in LDtk
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component)]
+pub enum AnchorKind {
+    #[default]
+    Swing,
+    // Reels the player straight in to the anchor
+    Pull,
+}
+
+impl From<&EntityInstance> for AnchorKind {
+    fn from(entity_instance: &EntityInstance) -> Self {
+        if *entity_instance.get_bool_field("Pull").unwrap_or(&false) {
+            AnchorKind::Pull
+        } else {
+            AnchorKind::Swing
+        }
+    }
+}
+
+#[derive(Clone, Default, Bundle, LdtkEntity)]
+pub struct GrapplePointBundle {
+    hookable: Hookable,
+    #[from_entity_instance]
+    kind: AnchorKind,
+    #[sprite_bundle("hook.png")]
+    sprite_bundle: SpriteBundle,
+    #[with(grapple_point_physics)]
+    physics: (RigidBody, Collider, Sensor, CollisionGroups),
+}
+
+// A sensor so the player's ground and climb checks ignore it, and only the hook can hit it
+fn grapple_point_physics(_: &EntityInstance) -> (RigidBody, Collider, Sensor, CollisionGroups) {
+    (
+        RigidBody::Fixed,
+        Collider::ball(8.0),
+        Sensor,
+        CollisionGroups::new(Group::GROUP_4, Group::GROUP_3),
+    )
+}
+
+// A cable between two points relative to the entity, ridden downhill once hooked.
+// Its length is the entity's width in LDtk and the "Drop" field lowers the right end
+#[derive(Clone, Default, Debug, Component)]
+pub struct Zipline {
+    start: Vec2,
+    end: Vec2,
+}
+
+impl From<&EntityInstance> for Zipline {
+    fn from(entity_instance: &EntityInstance) -> Self {
+        let half_width = entity_instance.width as f32 / 2.0;
+        let drop = *entity_instance.get_int_field("Drop").unwrap_or(&0) as f32;
+        Zipline {
+            start: Vec2::new(-half_width, drop / 2.0),
+            end: Vec2::new(half_width, -drop / 2.0),
+        }
+    }
+}
+
+#[derive(Clone, Default, Bundle, LdtkEntity)]
+pub struct ZiplineBundle {
+    #[from_entity_instance]
+    zipline: Zipline,
+    hookable: Hookable,
+    #[with(zipline_physics)]
+    physics: (RigidBody, Collider, Sensor, CollisionGroups),
+    visibility: VisibilityBundle,
+}
+
+fn zipline_physics(entity_instance: &EntityInstance) -> (RigidBody, Collider, Sensor, CollisionGroups) {
+    let zipline = Zipline::from(entity_instance);
+    (
+        RigidBody::Fixed,
+        Collider::segment(zipline.start, zipline.end),
+        Sensor,
+        CollisionGroups::new(Group::GROUP_4, Group::GROUP_3),
+    )
+}
+
+// One link of the rope drawn between the player's hand and the hook, a child of the hook
+#[derive(Component)]
+pub struct RopeSegment {
+    index: usize,
+}
+
+// Marks the sprite showing where the hook will fire
+#[derive(Component)]
+pub struct AimReticle;
+
+// Speeds, ranges and rope limits come from MovementTuning, these are the hook's fixed geometry
+const HOOK_SPAWN_OFFSET: f32 = 14.0;
+const RETICLE_DISTANCE: f32 = 40.0;
+
+// Pull anchors reel the player in until this close
+const PULL_ARRIVE_DISTANCE: f32 = 20.0;
+
+// Ziplines carry the player hanging below the hook, pulled back under it when they drift
+const ZIPLINE_HANG: f32 = 20.0;
+const ZIPLINE_FOLLOW: f32 = 12.0;
+
+// A returning hook is caught once it's this close to the player
+const HOOK_CATCH_DISTANCE: f32 = 16.0;
+
+// Rope is drawn as a chain of thin sprites, sagging by however much rope is slack.
+// A flying hook pays out a bit more rope than the distance it has covered
+const ROPE_SEGMENTS: usize = 24;
+const ROPE_THICKNESS: f32 = 2.0;
+const ROPE_Z: f32 = 5.0;
+const ROPE_HAND_OFFSET: Vec2 = Vec2::new(6.0, 2.0);
+const ROPE_FLYING_SLACK: f32 = 0.08;
+
+// Direction the hook fires in: the held aim, or diagonally up-forward when nothing is held
+pub fn aim_direction(input: &PlayerInput, facing: f32) -> Vec2 {
+    if input.aim != Vec2::ZERO {
+        input.aim
+    } else {
+        Vec2::new(facing, 1.0).normalize()
+    }
 }
 
 #[derive(Clone, Component, Copy, Debug, Hash, Default)]
@@ -32,71 +165,132 @@ pub enum HookState {
     Shooting,
     Latched,
     Swinging,
+    Pulling,
+    Ziplining,
+    Retracting,
+}
+
+// Added to the player when the hook returns, blocks firing again until it runs out
+#[derive(Component)]
+pub struct GrappleCooldown {
+    timer: Timer,
+}
+
+impl GrappleCooldown {
+    fn new(seconds: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+        }
+    }
 }
 
 pub fn grapple_launch(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    parent_query: Query<(&Player, &Transform, &Velocity, &Sprite, &PlayerInput), With<Player>>,
+    tuning: Res<MovementTuning>,
+    parent_query: Query<(&Abilities, &Transform, &Velocity, &Sprite, &PlayerInput), (With<Player>, Without<GrappleCooldown>)>,
+    grapple_query: Query<(), With<Grapple>>,
 ) {
-    for (player, player_transform, player_velocity, player_sprite, input) in parent_query.iter() {
-        if player.progression < 3 {continue;}
+    // One hook at a time, including while the last one is still reeling back
+    if !grapple_query.is_empty() {
+        return;
+    }
+    for (abilities, player_transform, player_velocity, player_sprite, input) in parent_query.iter() {
+        if !abilities.has(Ability::Grapple) {continue;}
         let direction = if !player_sprite.flip_x { 1.0 } else { -1.0 };
-        let additional_velocity = Vec2::new(300.0 * direction, 300.0);
+        let aim = aim_direction(input, direction);
+        let flying_direction = if aim.x != 0.0 { aim.x.signum() } else { direction };
+        let additional_velocity = aim * tuning.grapple.launch_speed;
         if input.grapple {
+            let spawn_position = player_transform.translation.truncate() + aim * HOOK_SPAWN_OFFSET;
             commands.spawn(
                 GrappleBundle {
-                    grapple: Grapple { flying_direction: direction },
+                    grapple: Grapple { flying_direction, aim, rope_length: 0.0, anchor: None },
                     state: Default::default(),
                     sprite: SpriteBundle {
                         texture: asset_server.load("hook.png"),
                         sprite: Sprite {
                             custom_size: Some(Vec2::new(12.0, 12.0)),
-                            flip_x: player_sprite.flip_x,
+                            flip_x: flying_direction < 0.0,
                             ..Default::default()
                         },
-                        transform: Transform::from_xyz(player_transform.translation.x + (10. * direction), player_transform.translation.y + 10., 6.0),
+                        transform: Transform::from_xyz(spawn_position.x, spawn_position.y, 6.0),
                         ..Default::default()
                     },
                     rigid_body: RigidBody::KinematicVelocityBased,
                     velocity: Velocity::linear(player_velocity.linvel + additional_velocity),
                     collider: Collider::ball(3.0),
-                    collider_groups: CollisionGroups::new(Group::GROUP_3, Group::GROUP_2),
+                    collider_groups: CollisionGroups::new(Group::GROUP_3, Group::GROUP_2 | Group::GROUP_4),
                     rotation_constraints: LockedAxes::ROTATION_LOCKED_X | LockedAxes::ROTATION_LOCKED_Y,
                     active_events: ActiveEvents::COLLISION_EVENTS,
                     active_collision_types: ActiveCollisionTypes::KINEMATIC_STATIC,
                 }
-            );
+            ).with_children(|parent| {
+                for index in 0..ROPE_SEGMENTS {
+                    // Alternating shades give the rope a braided look
+                    let color = if index % 2 == 0 { Color::srgb(0.55, 0.38, 0.2) } else { Color::srgb(0.45, 0.3, 0.15) };
+                    parent.spawn((
+                        SpriteBundle {
+                            sprite: Sprite {
+                                color,
+                                custom_size: Some(Vec2::ZERO),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        RopeSegment { index },
+                    ));
+                }
+            });
         }
     }
 }
 
 pub fn update_grapple(
     mut commands: Commands,
-    mut gizmos: Gizmos,
-    mut grapple_query: Query<(Entity, &Grapple, &Transform, &mut HookState, &mut RigidBody, &mut Velocity,), With<Grapple>>,
+    time: Res<Time>,
+    tuning: Res<MovementTuning>,
+    mut grapple_query: Query<(Entity, &mut Grapple, &Transform, &mut HookState, &mut RigidBody, &mut Velocity, &mut CollisionGroups), With<Grapple>>,
     mut collision_event: EventReader<CollisionEvent>,
-    mut player_query: Query<(Entity, &mut Player, &Transform, &mut Velocity, &PlayerInput, &GroundDetection, &ClimbDetection), (With<Player>, Without<Grapple>)>,
+    hookable_query: Query<Option<&AnchorKind>, With<Hookable>>,
+    zipline_query: Query<(&Zipline, &GlobalTransform)>,
+    mut player_query: Query<(Entity, &mut Player, &Transform, &mut Velocity, &PlayerInput, &GroundDetection, &ClimbDetection, Option<&mut ImpulseJoint>), (With<Player>, Without<Grapple>)>,
 ) {
-    for (grapple_entity, grapple, grapple_position, mut state, mut rigidbody, mut grapple_velocity, ) in grapple_query.iter_mut() {
-        for (player_entity, mut player, player_position, mut player_velocity, player_input, ground_detection, climb_detection) in player_query.iter_mut() {
+    let tuning = &tuning.grapple;
+    for (grapple_entity, mut grapple, grapple_position, mut state, mut rigidbody, mut grapple_velocity, mut collision_groups) in grapple_query.iter_mut() {
+        for (player_entity, mut player, player_position, mut player_velocity, player_input, ground_detection, climb_detection, rope_joint) in player_query.iter_mut() {
             match *state {
                 HookState::Shooting => {
-                    gizmos.line_2d(
-                        Vec2::new(grapple_position.translation.x, grapple_position.translation.y),
-                        Vec2::new(player_position.translation.x, player_position.translation.y),
-                        Color::BLACK,
-                    );
-                    grapple_velocity.linvel.x = player_velocity.linvel.x + 700. * grapple.flying_direction;
-                    grapple_velocity.linvel.y = 700.;
+                    grapple_velocity.linvel = grapple.aim * tuning.hook_speed + Vec2::new(player_velocity.linvel.x, 0.0);
+                    if grapple_position.translation.truncate().distance(player_position.translation.truncate()) > tuning.max_range {
+                        retract(&mut state, &mut collision_groups);
+                    }
                     // Check for collisions
                     for collision_event in collision_event.read() {
                         match collision_event {
                             CollisionEvent::Started(e1, e2, _) => {
-                                if grapple_entity == *e1 {
-                                    *state = HookState::Latched;
+                                if *state != HookState::Shooting {
+                                    continue;
+                                }
+                                let other = if grapple_entity == *e1 {
+                                    *e2
                                 } else if grapple_entity == *e2 {
-                                    *state = HookState::Latched;
+                                    *e1
+                                } else {
+                                    continue;
+                                };
+                                if zipline_query.contains(other) {
+                                    grapple.anchor = Some(other);
+                                    *state = HookState::Ziplining;
+                                    // Rides the cable without catching on anything else
+                                    *collision_groups = CollisionGroups::new(Group::GROUP_3, Group::NONE);
+                                } else if let Ok(kind) = hookable_query.get(other) {
+                                    *state = match kind {
+                                        Some(AnchorKind::Pull) => HookState::Pulling,
+                                        _ => HookState::Latched,
+                                    };
+                                } else {
+                                    retract(&mut state, &mut collision_groups);
                                 }
                             }
                             _ => {}
@@ -113,6 +307,7 @@ pub fn update_grapple(
                     commands.entity(player_entity).insert(
                         ImpulseJoint::new(grapple_entity, *joint.build().set_contacts_enabled(false).set_max_distance(distance)),
                     );
+                    grapple.rope_length = distance;
                     player.double_jumped = false;
                     if !ground_detection.on_ground {
                         player_velocity.linvel.y = -300.0;
@@ -124,27 +319,255 @@ pub fn update_grapple(
                 }
                 HookState::Swinging => {
                     *rigidbody = RigidBody::Fixed;
-                    player_velocity.linvel.x = player_velocity.linvel.x + 5. * grapple.flying_direction;
-                    gizmos.line_2d(
-                        Vec2::new(grapple_position.translation.x, grapple_position.translation.y),
-                        Vec2::new(player_position.translation.x, player_position.translation.y),
-                        Color::BLACK,
-                    );
+                    let rope = player_position.translation.truncate() - grapple_position.translation.truncate();
+                    let pump = player_input.move_right as i8 as f32 - player_input.move_left as i8 as f32;
+                    // Pumping above the anchor would only fight the rope
+                    if pump != 0.0 && rope.y < 0.0 {
+                        let tangent = swing_tangent(rope);
+                        let tangent = if tangent.x * pump < 0.0 { -tangent } else { tangent };
+                        player_velocity.linvel += tangent * tuning.swing_pump_acceleration * time.delta_seconds();
+                    }
+                    player_velocity.linvel = player_velocity.linvel.clamp_length_max(tuning.swing_max_speed);
+
+                    // Reel in with up, out with down. A rope latched outside the limits can still reel back inside them
+                    let reel = player_input.up as i8 as f32 - player_input.fast_fall as i8 as f32;
+                    if reel != 0.0 {
+                        let min_length = tuning.rope_min_length.min(grapple.rope_length);
+                        let max_length = tuning.rope_max_length.max(grapple.rope_length);
+                        grapple.rope_length = (grapple.rope_length - reel * tuning.reel_speed * time.delta_seconds())
+                            .clamp(min_length, max_length);
+                        if let Some(mut rope_joint) = rope_joint {
+                            if let TypedJoint::RopeJoint(rope) = &mut rope_joint.data {
+                                rope.set_max_distance(grapple.rope_length);
+                            }
+                        }
+                    }
+                }
+                HookState::Pulling => {
+                    *rigidbody = RigidBody::Fixed;
+                    let to_anchor = grapple_position.translation.truncate() - player_position.translation.truncate();
+                    player.double_jumped = false;
+                    if to_anchor.length() < PULL_ARRIVE_DISTANCE {
+                        player_velocity.linvel = to_anchor.normalize_or_zero() * tuning.pull_exit_speed;
+                        retract(&mut state, &mut collision_groups);
+                    } else {
+                        player_velocity.linvel = to_anchor.normalize() * tuning.pull_speed;
+                    }
+                }
+                HookState::Ziplining => {
+                    *rigidbody = RigidBody::KinematicVelocityBased;
+                    let (zipline, zipline_transform) = if let Some(Ok(zipline)) = grapple.anchor.map(|anchor| zipline_query.get(anchor)) {
+                        zipline
+                    } else {
+                        retract(&mut state, &mut collision_groups);
+                        continue;
+                    };
+                    let start = zipline_transform.translation().truncate() + zipline.start;
+                    let line = zipline.end - zipline.start;
+                    // Downhill, or onwards the way the hook was thrown on a level cable
+                    let heading = if line.y < -1.0 {
+                        1.0
+                    } else if line.y > 1.0 {
+                        -1.0
+                    } else {
+                        grapple.flying_direction
+                    };
+                    let hook = grapple_position.translation.truncate();
+                    let progress = (hook - start).dot(line) / line.length_squared();
+                    if (heading > 0.0 && progress >= 1.0) || (heading < 0.0 && progress <= 0.0) {
+                        // Off the end, the player keeps the ride's momentum
+                        retract(&mut state, &mut collision_groups);
+                    } else {
+                        grapple_velocity.linvel = line.normalize() * heading * tuning.zipline_speed;
+                        let hang_position = hook - Vec2::Y * ZIPLINE_HANG;
+                        player_velocity.linvel = grapple_velocity.linvel + (hang_position - player_position.translation.truncate()) * ZIPLINE_FOLLOW;
+                        player.double_jumped = false;
+                    }
+                }
+                HookState::Retracting => {
+                    *rigidbody = RigidBody::KinematicVelocityBased;
+                    let to_player = player_position.translation.truncate() - grapple_position.translation.truncate();
+                    if to_player.length() < HOOK_CATCH_DISTANCE {
+                        commands.entity(grapple_entity).despawn_recursive();
+                        commands.entity(player_entity).insert(GrappleCooldown::new(tuning.cooldown));
+                    } else {
+                        grapple_velocity.linvel = to_player.normalize() * tuning.retract_speed + player_velocity.linvel;
+                    }
                 }
             }
-            if player_input.jump || !player_input.grapple_held || (state.eq(&HookState::Swinging) && (climb_detection.climbing)) {
+            // Swinging into a wall lets go so the player can grab it, a wall behind the swing doesn't
+            let swung_into_wall = climb_detection.side.map_or(false, |side| side.direction() * player_velocity.linvel.x > 0.0);
+            let released = player_input.jump || !player_input.grapple_held || (state.eq(&HookState::Swinging) && swung_into_wall);
+            if released && *state != HookState::Retracting {
+                if *state == HookState::Swinging {
+                    let tangent = swing_tangent(player_position.translation.truncate() - grapple_position.translation.truncate());
+                    let swing_speed = player_velocity.linvel.dot(tangent);
+                    if swing_speed.abs() > tuning.swing_release_min_speed {
+                        player_velocity.linvel += tangent * swing_speed.signum() * tuning.swing_release_boost;
+                    }
+                }
                 commands.entity(player_entity).remove::<ImpulseJoint>();
-                commands.entity(grapple_entity).despawn_recursive();
+                retract(&mut state, &mut collision_groups);
             }
         }
     }
 }
 
+// Unit direction of travel along the swing arc for a rope pointing from the hook to the player
+fn swing_tangent(rope: Vec2) -> Vec2 {
+    Vec2::new(-rope.y, rope.x).normalize_or_zero()
+}
+
+// Sends the hook back to the player without colliding with anything on the way
+fn retract(state: &mut HookState, collision_groups: &mut CollisionGroups) {
+    *state = HookState::Retracting;
+    *collision_groups = CollisionGroups::new(Group::GROUP_3, Group::NONE);
+}
+
+// Lays the rope segments along a curve from the player's hand to the hook.
+// Taut while the rope is at full length, sagging by the slack otherwise
+fn update_rope(
+    player_query: Query<(&Transform, &Sprite), With<Player>>,
+    grapple_query: Query<(&Grapple, &HookState, &Transform), Without<Player>>,
+    mut segment_query: Query<(&RopeSegment, &Parent, &mut Transform, &mut Sprite), (Without<Grapple>, Without<Player>)>,
+) {
+    let (player_transform, player_sprite) = if let Ok(player) = player_query.get_single() {
+        player
+    } else {
+        return;
+    };
+    let facing = if !player_sprite.flip_x { 1.0 } else { -1.0 };
+    let hand = player_transform.translation.truncate() + ROPE_HAND_OFFSET * Vec2::new(facing, 1.0);
+
+    for (segment, parent, mut transform, mut sprite) in segment_query.iter_mut() {
+        let (grapple, state, grapple_transform) = if let Ok(grapple) = grapple_query.get(parent.get()) {
+            grapple
+        } else {
+            continue;
+        };
+        let hook = grapple_transform.translation.truncate();
+        let distance = hand.distance(hook);
+        let slack = match state {
+            HookState::Shooting | HookState::Retracting => distance * ROPE_FLYING_SLACK,
+            HookState::Latched | HookState::Swinging => (grapple.rope_length - distance).max(0.0),
+            HookState::Pulling | HookState::Ziplining => 0.0,
+        };
+        // Depth of a parabola whose arc is `slack` longer than its span
+        let sag = if distance > 0.0 { (3.0 * distance * slack / 8.0).sqrt() } else { 0.0 };
+        let point = |t: f32| hand.lerp(hook, t) - Vec2::Y * sag * 4.0 * t * (1.0 - t);
+
+        let start = point(segment.index as f32 / ROPE_SEGMENTS as f32);
+        let end = point((segment.index + 1) as f32 / ROPE_SEGMENTS as f32);
+        let span = end - start;
+        // Segments are children of the hook, so positions are relative to it
+        let center = (start + end) / 2.0 - hook;
+        transform.translation = center.extend(ROPE_Z - grapple_transform.translation.z);
+        transform.rotation = Quat::from_rotation_z(span.y.atan2(span.x));
+        // Overlap slightly so no gaps show between links
+        sprite.custom_size = Some(Vec2::new(span.length() + 0.5, ROPE_THICKNESS));
+    }
+}
+
+fn spawn_zipline_cables(mut commands: Commands, zipline_query: Query<(Entity, &Zipline), Added<Zipline>>) {
+    for (zipline_entity, zipline) in zipline_query.iter() {
+        let line = zipline.end - zipline.start;
+        commands.entity(zipline_entity).with_children(|parent| {
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: Color::srgb(0.35, 0.4, 0.45),
+                    custom_size: Some(Vec2::new(line.length(), ROPE_THICKNESS)),
+                    ..Default::default()
+                },
+                transform: Transform::from_translation(((zipline.start + zipline.end) / 2.0).extend(0.0))
+                    .with_rotation(Quat::from_rotation_z(line.y.atan2(line.x))),
+                ..Default::default()
+            });
+        });
+    }
+}
+
+// Pull anchors are gold so they read differently from swing points
+fn tint_pull_anchors(mut query: Query<(&AnchorKind, &mut Sprite), Added<AnchorKind>>) {
+    for (kind, mut sprite) in query.iter_mut() {
+        if *kind == AnchorKind::Pull {
+            sprite.color = Color::srgb(1.0, 0.8, 0.3);
+        }
+    }
+}
+
+fn tick_grapple_cooldown(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut GrappleCooldown)>,
+) {
+    for (entity, mut cooldown) in query.iter_mut() {
+        cooldown.timer.tick(time.delta());
+        if cooldown.timer.finished() {
+            commands.entity(entity).remove::<GrappleCooldown>();
+        }
+    }
+}
+
+fn spawn_aim_reticle(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    player_query: Query<Entity, Added<Player>>,
+) {
+    for player_entity in player_query.iter() {
+        commands.entity(player_entity).with_children(|parent| {
+            parent.spawn((
+                SpriteBundle {
+                    texture: asset_server.load("hook.png"),
+                    sprite: Sprite {
+                        color: Color::srgba(1.0, 1.0, 1.0, 0.6),
+                        custom_size: Some(Vec2::new(8.0, 8.0)),
+                        ..Default::default()
+                    },
+                    transform: Transform::from_xyz(0.0, 0.0, 1.0),
+                    visibility: Visibility::Hidden,
+                    ..Default::default()
+                },
+                AimReticle,
+            ));
+        });
+    }
+}
+
+fn update_aim_reticle(
+    player_query: Query<(&Abilities, &PlayerInput, &Sprite), With<Player>>,
+    grapple_query: Query<(), With<Grapple>>,
+    mut reticle_query: Query<(&mut Transform, &mut Visibility), With<AimReticle>>,
+) {
+    let (abilities, input, player_sprite) = if let Ok(player) = player_query.get_single() {
+        player
+    } else {
+        return;
+    };
+
+    for (mut transform, mut visibility) in reticle_query.iter_mut() {
+        // Only shown while the hook is ready to fire
+        if !abilities.has(Ability::Grapple) || !grapple_query.is_empty() {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+        let direction = if !player_sprite.flip_x { 1.0 } else { -1.0 };
+        let aim = aim_direction(input, direction) * RETICLE_DISTANCE;
+        transform.translation.x = aim.x;
+        transform.translation.y = aim.y;
+        *visibility = Visibility::Visible;
+    }
+}
+
 pub struct GrapplePlugin;
 impl Plugin for GrapplePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, grapple_launch);
-        app.add_systems(Update, update_grapple);
+        app.add_systems(Update, grapple_launch.run_if(in_state(GameState::Game)));
+        app.add_systems(Update, (update_grapple, tick_grapple_cooldown).run_if(in_state(GameState::Game)));
+        app.add_systems(Update, update_rope.after(update_grapple).run_if(in_state(GameState::Game)));
+        app.add_systems(Update, (spawn_aim_reticle, update_aim_reticle).run_if(in_state(GameState::Game)));
+        app.add_systems(Update, (spawn_zipline_cables, tint_pull_anchors));
+        app.register_ldtk_entity::<GrapplePointBundle>("GrapplePoint");
+        app.register_ldtk_entity::<ZiplineBundle>("Zipline");
     }
 }
 
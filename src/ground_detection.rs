@@ -93,6 +93,6 @@ impl Plugin for GroundDetectionPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Update, spawn_ground_sensor)
             .add_systems(Update, ground_detection)
-            .add_systems(Update, update_on_ground);
+            .add_systems(Update, update_on_ground.after(ground_detection));
     }
 }
@@ -1,5 +1,7 @@
+use crate::camera::CameraShake;
+use crate::death::{DeathCause, Dying, PlayerDied};
+use crate::game_menu::GameState;
 use crate::physics::SensorBundle;
-use crate::player::reset_position;
 use crate::Player;
 use bevy::prelude::*;
 use bevy_ecs_ldtk::prelude::*;
@@ -49,8 +51,9 @@ fn detect_lava(
     mut commands: Commands,
     rapier_context: Res<RapierContext>,
     lava_query: Query<Entity, With<Lava>>,
-    mut player_query: Query<(Entity, &mut Sprite), With<Player>>,
+    mut player_query: Query<(Entity, &mut Sprite), (With<Player>, Without<Dying>)>,
     burning_query: Query<&BurningEffect>,
+    mut shake_events: EventWriter<CameraShake>,
 ) {
     let (player_entity, mut sprite) = if let Ok(player) = player_query.get_single_mut() {
         player
@@ -79,6 +82,7 @@ fn detect_lava(
         commands.entity(player_entity)
             .insert(LavaContact::default())
             .insert(burning_effect);
+        shake_events.send(CameraShake { trauma: 0.4 });
     } else if !is_in_lava && is_burning {
         // Player just left lava
         if let Ok(_burning) = burning_query.get(player_entity) {
@@ -94,20 +98,19 @@ fn detect_lava(
 fn check_lava_timer(
     time: Res<Time>,
     mut commands: Commands,
-    mut player_query: Query<(Entity, &mut Transform, &mut Velocity, &mut LavaContact, &mut Sprite, Option<&BurningEffect>), With<Player>>,
+    mut death_events: EventWriter<PlayerDied>,
+    mut player_query: Query<(Entity, &mut LavaContact, &mut Sprite, Option<&BurningEffect>), With<Player>>,
 ) {
-    for (entity, mut transform, mut velocity, mut contact, mut sprite, burning_effect) in player_query.iter_mut() {
+    for (entity, mut contact, mut sprite, burning_effect) in player_query.iter_mut() {
         contact.timer.tick(time.delta());
 
         if contact.timer.just_finished() {
-            // Restore original color before resetting position
+            // Restore original color before the death animation plays
             if let Some(burning) = burning_effect {
                 sprite.color = burning.original_color;
             }
 
-            // Respawn after 1 second
-            *transform = reset_position(transform.clone());
-            velocity.linvel = Vec2::ZERO;
+            death_events.send(PlayerDied { cause: DeathCause::Lava });
 
             // Remove the contact timer
             commands.entity(entity).remove::<LavaContact>().remove::<BurningEffect>();
@@ -144,6 +147,6 @@ impl Plugin for LavaPlugin {
             detect_lava,
             check_lava_timer.after(detect_lava),
             burning_effect,
-        )).register_ldtk_int_cell::<LavaBundle>(2);
+        ).run_if(in_state(GameState::Game))).register_ldtk_int_cell::<LavaBundle>(2);
     }
 }
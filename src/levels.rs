@@ -1,10 +1,30 @@
-use crate::startup::LevelMusicMap;
 use bevy::app::{App, Plugin, Update};
-use bevy::prelude::{Assets, GlobalTransform, Handle, Query, Rect, Res, ResMut, Vec2, With};
+use bevy::prelude::{AssetServer, Assets, GlobalTransform, Handle, Query, Rect, Res, ResMut, Resource, Vec2, With};
+use bevy::log::{info, warn};
+use bevy::utils::HashMap;
 use bevy_ecs_ldtk::{LevelIid, LevelSelection};
-use bevy_ecs_ldtk::prelude::{LdtkProject, LevelMetadataAccessor};
-use bevy_kira_audio::{Audio, AudioControl};
+use bevy_ecs_ldtk::prelude::{LdtkFields, LdtkProject, LevelMetadataAccessor};
+use bevy_kira_audio::prelude::{AudioChannel, AudioControl, AudioEasing, AudioSource, AudioTween};
 use crate::player::Player;
+use crate::sound::MusicChannel;
+use std::time::Duration;
+
+// Level field with the path of the level's track, levels without one play the default track
+const MUSIC_FIELD: &str = "Music";
+const DEFAULT_LEVEL_MUSIC: &str = "sounds/grapple.ogg";
+// The old level's track fades out over this while the new one fades in
+const MUSIC_CROSSFADE_SECONDS: f32 = 1.5;
+
+// Handles of every track played so far, kept so going back to a level doesn't load its track again
+#[derive(Resource, Default)]
+pub struct LevelMusic {
+    tracks: HashMap<String, Handle<AudioSource>>,
+    playing: Option<String>,
+}
+
+fn music_crossfade() -> AudioTween {
+    AudioTween::new(Duration::from_secs_f32(MUSIC_CROSSFADE_SECONDS), AudioEasing::InOutPowi(2))
+}
 
 fn level_selection_follow_player(
     players: Query<&GlobalTransform, With<Player>>,
@@ -12,8 +32,9 @@ fn level_selection_follow_player(
     ldtk_projects: Query<&Handle<LdtkProject>>,
     ldtk_project_assets: Res<Assets<LdtkProject>>,
     mut level_selection: ResMut<LevelSelection>,
-    level_music_map: Res<LevelMusicMap>, // Access the level-to-music map
-    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+    mut level_music: ResMut<LevelMusic>,
+    music: Res<AudioChannel<MusicChannel>>,
 ) {
     if let Ok(player_transform) = players.get_single() {
         let ldtk_project = ldtk_project_assets
@@ -44,19 +65,30 @@ fn level_selection_follow_player(
                 if *level_selection != current_level {
                     *level_selection = current_level.clone();
 
-                    // Fetch the music handle for the new level
-                    if let Some(music_handle) = level_music_map.music_map.get(level_iid.get()) {
-                        // Stop the current music and play the new one
-                        audio.stop();
-                        audio.play(music_handle.clone()).looped().with_volume(0.8);
+                    let music_path = level
+                        .get_maybe_string_field(MUSIC_FIELD)
+                        .ok()
+                        .and_then(|path| path.as_deref())
+                        .filter(|path| !path.is_empty())
+                        .unwrap_or_else(|| {
+                            warn!("No music set for level: {}. Default music will be played.", level.identifier);
+                            DEFAULT_LEVEL_MUSIC
+                        });
+
+                    // Neighbouring levels sharing a track keep it playing
+                    if level_music.playing.as_deref() != Some(music_path) {
+                        let music_handle = level_music
+                            .tracks
+                            .entry(music_path.to_string())
+                            .or_insert_with(|| asset_server.load(music_path.to_string()))
+                            .clone();
+
+                        // Fade the current music out while the new one fades in
+                        music.stop().fade_out(music_crossfade());
+                        music.play(music_handle).looped().with_volume(0.8).fade_in(music_crossfade());
+                        level_music.playing = Some(music_path.to_string());
 
-                        print!("Playing music for level: {}", level_iid.get());
-                    } else {
-                        // Log a warning if no music is found for the level
-                        print!(
-                            "No music found for level: {}. Default music will be played.",
-                            level_iid.get()
-                        );
+                        info!("Playing {} for level: {}", music_path, level.identifier);
                     }
                 }
             }
@@ -69,6 +101,7 @@ pub struct LevelPlugin;
 impl Plugin for LevelPlugin {
     fn build(&self, app: &mut App) {
         app
+            .init_resource::<LevelMusic>()
             .add_systems(Update, level_selection_follow_player);
     }
 }
@@ -2,6 +2,9 @@ use crate::game_menu::{close_popup, rules_button_interaction, update_game};
 use crate::game_menu::handle_loading;
 use crate::game_menu::cleanup_menu;
 use crate::game_menu::button_interaction;
+use crate::game_menu::continue_button_interaction;
+use crate::game_menu::controls_button_interaction;
+use crate::game_menu::settings_button_interaction;
 use bevy::asset::AssetMetaCheck;
 use bevy::prelude::*;
 use bevy_ecs_ldtk::prelude::*;
@@ -10,6 +13,7 @@ use bevy_asset_loader::prelude::*;
 use bevy_kira_audio::AudioPlugin;
 
 mod startup;
+mod abilities;
 mod player;
 mod physics;
 mod walls;
@@ -20,8 +24,18 @@ mod grapple;
 mod lava;
 mod levels;
 mod collectibles;
+mod checkpoints;
 mod game_menu;
 mod progression_ui;
+mod save;
+mod pause_menu;
+mod controls;
+mod tuning;
+mod debug;
+mod death;
+mod camera;
+mod signs;
+mod sound;
 
 use startup::setup;
 use crate::player::Player;
@@ -39,7 +53,6 @@ fn main() {
             AudioPlugin,
             LdtkPlugin,
             RapierPhysicsPlugin::<()>::default(),
-            //RapierDebugRenderPlugin::default(), //for debugging colliders
         ))
         .init_state::<GameState>() // Add the GameState
 
@@ -52,10 +65,22 @@ fn main() {
             Update,
             button_interaction.run_if(in_state(GameState::MainMenu))
         )
+        .add_systems(
+            Update,
+            continue_button_interaction.run_if(in_state(GameState::MainMenu))
+        )
         .add_systems(
             Update,
             rules_button_interaction.run_if(in_state(GameState::MainMenu))
         )
+        .add_systems(
+            Update,
+            controls_button_interaction.run_if(in_state(GameState::MainMenu))
+        )
+        .add_systems(
+            Update,
+            settings_button_interaction.run_if(in_state(GameState::MainMenu))
+        )
         .add_systems(
             Update,
             close_popup.run_if(in_state(GameState::MainMenu))
@@ -64,6 +89,10 @@ fn main() {
             OnExit(GameState::MainMenu),
             cleanup_menu
         )
+        .add_systems(
+            OnEnter(GameState::Loading),
+            handle_loading
+        )
 
         .insert_resource(LevelSelection::index(0))
         .insert_resource(LdtkSettings {
@@ -86,6 +115,16 @@ fn main() {
         .add_plugins(ground_detection::GroundDetectionPlugin)
         .add_plugins(wall_climb::WallClimbPlugin)
         .add_plugins(collectibles::CollectiblePlugin)
+        .add_plugins(checkpoints::CheckpointPlugin)
+        .add_plugins(save::SavePlugin)
+        .add_plugins(pause_menu::PausePlugin)
+        .add_plugins(controls::ControlsPlugin)
+        .add_plugins(tuning::TuningPlugin)
+        .add_plugins(debug::DebugPlugin)
+        .add_plugins(death::DeathPlugin)
+        .add_plugins(camera::CameraPlugin)
+        .add_plugins(signs::SignPlugin)
+        .add_plugins(sound::SoundPlugin)
 
         .run();
 }
\ No newline at end of file
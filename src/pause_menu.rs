@@ -0,0 +1,211 @@
+//pause_menu.rs
+use crate::checkpoints::CurrentCheckpoint;
+use crate::collectibles::CollectedArtifacts;
+use crate::death::DeathCounter;
+use crate::debug::console_closed;
+use crate::game_menu::GameState;
+use crate::grapple::Grapple;
+use crate::levels::LevelMusic;
+use crate::player::{reset_position, Player};
+use crate::save::{PlayTime, SaveGame};
+use crate::sound::MusicChannel;
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use bevy_kira_audio::prelude::{AudioChannel, AudioControl, AudioEasing, AudioTween};
+use bevy_rapier2d::prelude::*;
+use std::time::Duration;
+
+#[derive(Component)]
+pub struct PauseMenu;
+
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PauseButton {
+    Resume,
+    Restart,
+    QuitToMenu,
+}
+
+fn toggle_pause(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    game_state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let start_pressed = gamepads
+        .iter()
+        .any(|gamepad| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::Start)));
+    if keyboard_input.just_pressed(KeyCode::Escape) || start_pressed {
+        match game_state.get() {
+            GameState::Game => next_state.set(GameState::Paused),
+            GameState::Paused => next_state.set(GameState::Game),
+            _ => {}
+        }
+    }
+}
+
+fn pause_physics(mut rapier_config: ResMut<RapierConfiguration>) {
+    rapier_config.physics_pipeline_active = false;
+}
+
+fn resume_physics(mut rapier_config: ResMut<RapierConfiguration>) {
+    rapier_config.physics_pipeline_active = true;
+}
+
+pub fn show_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+
+    commands.spawn((
+        // Spawn a container for both the background and the menu
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.7).into(), // Dimmed background
+            ..Default::default()
+        },
+        PauseMenu,
+    ))
+        .with_children(|parent| {
+            // Menu container
+            parent.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(400.0),
+                    height: Val::Px(360.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(20.0)),
+                    flex_direction: FlexDirection::Column,
+                    ..Default::default()
+                },
+                background_color: Color::srgb(0.2, 0.2, 0.3).into(), // Dark blue-gray
+                ..Default::default()
+            })
+                .with_children(|menu| {
+                    menu.spawn(TextBundle::from_section(
+                        "Paused",
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 40.0,
+                            color: Color::WHITE,
+                        },
+                    ).with_style(Style {
+                        margin: UiRect::all(Val::Px(10.0)),
+                        ..Default::default()
+                    }));
+
+                    for (button, label) in [
+                        (PauseButton::Resume, "Resume"),
+                        (PauseButton::Restart, "Restart from checkpoint"),
+                        (PauseButton::QuitToMenu, "Quit to Main Menu"),
+                    ] {
+                        menu.spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    width: Val::Px(300.0),
+                                    height: Val::Px(60.0),
+                                    margin: UiRect::all(Val::Px(10.0)),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..Default::default()
+                                },
+                                background_color: Color::srgb(0.9, 0.9, 0.9).into(),
+                                ..Default::default()
+                            },
+                            button,
+                        ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    label,
+                                    TextStyle {
+                                        font: font.clone(),
+                                        font_size: 24.0,
+                                        color: Color::BLACK,
+                                    },
+                                ));
+                            });
+                    }
+                });
+        });
+}
+
+fn cleanup_pause_menu(mut commands: Commands, menu_query: Query<Entity, With<PauseMenu>>) {
+    for entity in menu_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn pause_button_interaction(
+    mut commands: Commands,
+    mut interaction_query: Query<(&Interaction, &PauseButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut save_events: EventWriter<SaveGame>,
+    checkpoint: Res<CurrentCheckpoint>,
+    grapple_query: Query<Entity, With<Grapple>>,
+    mut player_query: Query<(Entity, &mut Transform, &mut Velocity, &mut ExternalForce), With<Player>>,
+) {
+    for (interaction, button, mut color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => match button {
+                PauseButton::Resume => next_state.set(GameState::Game),
+                PauseButton::Restart => {
+                    for grapple_entity in grapple_query.iter() {
+                        commands.entity(grapple_entity).despawn_recursive();
+                    }
+                    for (player_entity, mut transform, mut velocity, mut force) in player_query.iter_mut() {
+                        commands.entity(player_entity).remove::<ImpulseJoint>();
+                        *transform = reset_position(transform.clone(), &checkpoint);
+                        velocity.linvel = Vec2::ZERO;
+                        force.force = Vec2::ZERO;
+                    }
+                    next_state.set(GameState::Game);
+                }
+                PauseButton::QuitToMenu => {
+                    // Saved before the world is torn down on entering the menu
+                    save_events.send(SaveGame);
+                    next_state.set(GameState::MainMenu);
+                }
+            },
+            Interaction::Hovered => {
+                *color = Color::srgb(0.7, 0.7, 0.7).into();
+            }
+            Interaction::None => {
+                *color = Color::srgb(0.9, 0.9, 0.9).into();
+            }
+        }
+    }
+}
+
+// Tears down the running game so "Start Game" begins from a fresh world
+fn cleanup_world(
+    mut commands: Commands,
+    music: Res<AudioChannel<MusicChannel>>,
+    world_query: Query<Entity, With<Handle<LdtkProject>>>,
+    grapple_query: Query<Entity, With<Grapple>>,
+) {
+    for entity in world_query.iter().chain(grapple_query.iter()) {
+        commands.entity(entity).despawn_recursive();
+    }
+    music.stop().fade_out(AudioTween::new(Duration::from_secs_f32(0.5), AudioEasing::OutPowi(2)));
+    commands.insert_resource(LevelMusic::default());
+    commands.insert_resource(CurrentCheckpoint::default());
+    commands.insert_resource(CollectedArtifacts::default());
+    commands.insert_resource(PlayTime::default());
+    commands.insert_resource(DeathCounter::default());
+}
+
+pub struct PausePlugin;
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, toggle_pause.run_if(in_state(GameState::Game).or_else(in_state(GameState::Paused)).and_then(console_closed)))
+            .add_systems(Update, pause_button_interaction.run_if(in_state(GameState::Paused)))
+            .add_systems(OnEnter(GameState::Paused), (pause_physics, show_pause_menu))
+            .add_systems(OnExit(GameState::Paused), (resume_physics, cleanup_pause_menu))
+            .add_systems(OnEnter(GameState::MainMenu), cleanup_world);
+    }
+}
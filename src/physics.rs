@@ -69,6 +69,12 @@ impl From<&EntityInstance> for SensorBundle {
                 rotation_constraints: LockedAxes::ROTATION_LOCKED,
                 //..Default::default()
             },
+            "Checkpoint" => SensorBundle {
+                collider: Collider::cuboid(8., 16.),
+                sensor: Sensor,
+                active_events: ActiveEvents::COLLISION_EVENTS,
+                rotation_constraints: LockedAxes::ROTATION_LOCKED,
+            },
             _ => Self::default(),
         }
     }
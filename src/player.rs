@@ -1,23 +1,32 @@
 // player.rs
+use crate::abilities::{Abilities, Ability};
 use crate::animation::*;
-use crate::ground_detection::GroundDetection;
+use crate::camera::CameraShake;
+use crate::checkpoints::CurrentCheckpoint;
+use crate::controls::{Action, ActionInput, STICK_DEADZONE};
+use crate::death::{DeathCause, Dying, PlayerDied};
+use crate::debug::DebugConsole;
+use crate::game_menu::GameState;
+use crate::ground_detection::{update_on_ground, GroundDetection};
 use crate::physics::PhysicsBundle;
-use crate::wall_climb::ClimbDetection;
+use crate::sound::SfxChannel;
+use crate::tuning::MovementTuning;
+use crate::wall_climb::{ClimbDetection, WallSide};
 use bevy::prelude::*;
 use bevy_ecs_ldtk::prelude::*;
 use bevy_rapier2d::prelude::*;
-use bevy_kira_audio::{Audio, AudioControl};
-
-//use crate::startup::{setup, LevelBounds};
+use bevy_kira_audio::{AudioChannel, AudioControl};
 
 #[derive(Clone, Default, Bundle, LdtkEntity)]
 pub struct PlayerBundle {
     player: Player,
+    abilities: Abilities,
     player_input: PlayerInput,
     #[from_entity_instance]
     physics: PhysicsBundle,
     animation_bundle: AnimationBundle,
     movement_intent: MovementIntent,
+    jump_timers: JumpTimers,
     ground_detection: GroundDetection,
     climb_detection: ClimbDetection,
     #[worldly]
@@ -30,6 +39,7 @@ pub struct PlayerBundle {
 pub struct Player {
     pub progression: u8,
     pub double_jumped: bool,
+    pub trial_complete: bool,
 }
 
 #[derive(Component, Default, Clone)]
@@ -38,10 +48,12 @@ pub struct PlayerInput {
     pub move_right: bool,
     pub jump: bool,
     pub jump_held: bool,
+    pub up: bool,
     pub fast_fall: bool,
     pub grapple: bool,
     pub grapple_held: bool,
     pub restart: bool,
+    pub aim: Vec2,
 }
 
 #[derive(Component, Default, Clone)]
@@ -51,64 +63,68 @@ pub struct MovementIntent {
     pub wants_to_jump: bool,
 }
 
-const PLAYER_ACCELERATION_MULTIPLIER: f32 = 400.0f32; //for force multiplier
-const PLAYER_TOP_SPEED: f32 = 250.0;
-const PLAYER_JUMP_STRENGTH: f32 = 270.0;
-
-
-const CAMERA_LERP_SPEED: f32 = 0.1;
-
-pub fn camera_follow_system(
-    player_query: Query<&Transform, With<Player>>,
-    mut camera_query: Query<&mut Transform, (With<Camera>, Without<Player>)>,
-    //level_bounds: Res<LevelBounds>, // Add this to access LevelBounds
-) {
-    if let Ok(player_transform) = player_query.get_single() {
-        if let Ok(mut camera_transform) = camera_query.get_single_mut() {
-            // Lerp the camera position towards the player's position
-            camera_transform.translation.x +=
-                (player_transform.translation.x - camera_transform.translation.x)
-                    * CAMERA_LERP_SPEED;
-            camera_transform.translation.y +=
-                (player_transform.translation.y - camera_transform.translation.y)
-                    * CAMERA_LERP_SPEED;
-
-            // Use LevelBounds to clamp the camera's position
-            // let min_x = level_bounds.padding_x;
-            // let max_x = level_bounds.width - level_bounds.padding_x;
-            // let min_y = level_bounds.padding_y;
-            // let max_y = level_bounds.height - level_bounds.padding_y;
-
-            // camera_transform.translation.x = camera_transform.translation.x.clamp(min_x, max_x);
-            // camera_transform.translation.y = camera_transform.translation.y.clamp(min_y, max_y);
-        }
-    }
+// Seconds left in each jump grace window, counted down in player_movement
+#[derive(Component, Default, Clone)]
+pub struct JumpTimers {
+    pub ground_coyote: f32,
+    pub wall_coyote: f32,
+    pub buffer: f32,
+    // Horizontal input is ignored for this long after a wall jump so the kick-off isn't cancelled
+    pub input_lock: f32,
+    // Which way the last wall clung to was, 1 for right and -1 for left
+    pub wall_direction: f32,
+    // Left over from the last jump, the sensors can't refill the windows or the double jump until it runs out
+    pub refill_lock: f32,
 }
 
 
 pub fn player_input(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut query: Query<&mut PlayerInput, With<Player>>,
+    controls: ActionInput,
+    console: Res<DebugConsole>,
+    mut query: Query<(&mut PlayerInput, Has<Dying>), With<Player>>,
 ) {
-    for mut input in query.iter_mut() {
-        input.move_left = keyboard_input.pressed(KeyCode::KeyA) || keyboard_input.pressed(KeyCode::ArrowLeft);
-        input.move_right = keyboard_input.pressed(KeyCode::KeyD) || keyboard_input.pressed(KeyCode::ArrowRight);
-        input.jump = keyboard_input.just_pressed(KeyCode::Space);
-        input.jump_held = keyboard_input.pressed(KeyCode::Space);
-        input.fast_fall = keyboard_input.pressed(KeyCode::ArrowDown) || keyboard_input.pressed(KeyCode::KeyS);
-        input.grapple = keyboard_input.just_pressed(KeyCode::KeyJ) || keyboard_input.just_pressed(KeyCode::ShiftLeft);
-        input.grapple_held = keyboard_input.pressed(KeyCode::KeyJ) || keyboard_input.pressed(KeyCode::ShiftLeft);
-        input.restart = keyboard_input.just_pressed(KeyCode::KeyR);
+    let stick = controls.left_stick();
+    for (mut input, dying) in query.iter_mut() {
+        // Typing in the debug console shouldn't move the player, and neither can a dead one
+        if console.open || dying {
+            *input = PlayerInput::default();
+            continue;
+        }
+        input.move_left = controls.pressed(Action::MoveLeft) || stick.x < -STICK_DEADZONE;
+        input.move_right = controls.pressed(Action::MoveRight) || stick.x > STICK_DEADZONE;
+        input.jump = controls.just_pressed(Action::Jump);
+        input.jump_held = controls.pressed(Action::Jump);
+        input.up = controls.pressed(Action::Up) || stick.y > STICK_DEADZONE;
+        input.fast_fall = controls.pressed(Action::FastFall) || stick.y < -STICK_DEADZONE;
+        input.grapple = controls.just_pressed(Action::Grapple);
+        input.grapple_held = controls.pressed(Action::Grapple);
+        input.restart = controls.just_pressed(Action::Restart);
+
+        // 8-directional aim from the held direction keys, overridden by the right stick
+        let right_stick = controls.right_stick();
+        input.aim = if right_stick != Vec2::ZERO {
+            right_stick.normalize()
+        } else {
+            Vec2::new(
+                input.move_right as i8 as f32 - input.move_left as i8 as f32,
+                input.up as i8 as f32 - input.fast_fall as i8 as f32,
+            )
+            .normalize_or_zero()
+        };
     }
 }
 
 pub fn player_movement(
-    audio: Res<Audio>,
+    audio: Res<AudioChannel<SfxChannel>>,
     asset_server: Res<AssetServer>,
+    time: Res<Time>,
+    checkpoint: Res<CurrentCheckpoint>,
+    tuning: Res<MovementTuning>,
     mut query: Query<(
-        //&Abilities,
+        &Abilities,
         &PlayerInput,
         &mut MovementIntent,
+        &mut JumpTimers,
         &mut Player,
         &mut Velocity,
         &mut Transform,
@@ -116,15 +132,16 @@ pub fn player_movement(
         &ClimbDetection,
         &mut ExternalForce,
         &mut GravityScale,
-        &mut Damping,
         &mut Sprite,
+        Has<ImpulseJoint>,
     )>,
 ) {
     //let walk_sound = asset_server.load("player_walk.ogg");
     for (
-        //abilities,
+        abilities,
         input,
         mut intent,
+        mut timers,
         mut player,
         mut velocity,
         mut transform,
@@ -132,22 +149,36 @@ pub fn player_movement(
         climb_detection,
         mut force,
         mut gravity,
-        mut damping,
         mut sprite,
+        on_rope,
     ) in query.iter_mut() {
+        let tuning = &tuning.player;
         let mut is_moving_now = false;
+        let delta = time.delta_seconds();
+        timers.input_lock = (timers.input_lock - delta).max(0.0);
 
 
         //implementation of forces for horizontal movement, meaning the player gradually speeds up instead of achieving max move speed instantly
-        if input.move_right
+        if on_rope {
+            //the grapple drives horizontal motion while swinging, ground movement would damp the swing
+            force.force.x = 0.0;
+            if input.move_right {
+                sprite.flip_x = false;
+            } else if input.move_left {
+                sprite.flip_x = true;
+            }
+        } else if timers.input_lock > 0.0 {
+            //keep the wall jump's kick-off
+            force.force.x = 0.0;
+        } else if input.move_right
         {
             //audio.play(walk_sound.clone());
             let new_horizontal_force = calc_force_diff(
                 intent.horizontal,
                 velocity.linvel.x,
-                PLAYER_TOP_SPEED,
+                tuning.top_speed,
             );
-            force.force.x = new_horizontal_force * PLAYER_ACCELERATION_MULTIPLIER;
+            force.force.x = new_horizontal_force * tuning.acceleration_multiplier;
             sprite.flip_x = false;
         } else if input.move_left
         {
@@ -155,54 +186,82 @@ pub fn player_movement(
             let new_horizontal_force = calc_force_diff(
                 intent.horizontal,
                 velocity.linvel.x,
-                -PLAYER_TOP_SPEED,
+                -tuning.top_speed,
             );
 
-            force.force.x = new_horizontal_force * PLAYER_ACCELERATION_MULTIPLIER;
+            force.force.x = new_horizontal_force * tuning.acceleration_multiplier;
             sprite.flip_x = true;
         } else {
             //audio.stop();
             if velocity.linvel.x.abs() > 0.01 {
                 let new_horizontal_force =
                     -velocity.linvel.x;
-                force.force.x = new_horizontal_force * PLAYER_ACCELERATION_MULTIPLIER;
+                force.force.x = new_horizontal_force * tuning.acceleration_multiplier;
             }
         }
 
         // Handle jumping
-        intent.wants_to_jump = input.jump && (ground_detection.on_ground || (climb_detection.climbing && player.progression >= 2) || (!player.double_jumped && player.progression >= 1));
+        let can_climb = climb_detection.climbing() && abilities.has(Ability::WallClimb);
+        if let Some(side) = climb_detection.side {
+            if can_climb {
+                timers.wall_direction = side.direction();
+            }
+        }
+        timers.refill_lock = (timers.refill_lock - delta).max(0.0);
+        let can_refill = timers.refill_lock <= 0.0;
+        let on_ground = ground_detection.on_ground && can_refill;
+        let on_climbable = can_climb && can_refill;
+        timers.ground_coyote = if on_ground { tuning.coyote_time } else { (timers.ground_coyote - delta).max(0.0) };
+        timers.wall_coyote = if on_climbable { tuning.coyote_time } else { (timers.wall_coyote - delta).max(0.0) };
+        timers.buffer = if input.jump { tuning.jump_buffer } else { (timers.buffer - delta).max(0.0) };
+
+        let grounded = timers.ground_coyote > 0.0;
+        let on_wall = timers.wall_coyote > 0.0;
+        intent.wants_to_jump = timers.buffer > 0.0 && (grounded || on_wall || (!player.double_jumped && abilities.has(Ability::DoubleJump)));
         if intent.wants_to_jump {
-            player.double_jumped = true;
-            velocity.linvel.y = PLAYER_JUMP_STRENGTH;
+            // Coyote jumps still count as the ground or wall jump
+            if !grounded && !on_wall {
+                player.double_jumped = true;
+            }
+            if on_wall && !grounded {
+                velocity.linvel = Vec2::new(-timers.wall_direction * tuning.wall_jump_kick, tuning.wall_jump_strength);
+                timers.input_lock = tuning.wall_jump_input_lock;
+                sprite.flip_x = timers.wall_direction > 0.0;
+            } else {
+                velocity.linvel.y = tuning.jump_strength;
+            }
+            // Each window is spent by the jump it allowed
+            timers.buffer = 0.0;
+            timers.ground_coyote = 0.0;
+            timers.wall_coyote = 0.0;
+            timers.refill_lock = tuning.jump_refill_lock;
         }
 
         // Reset double jump if on ground or climbing
-        if (ground_detection.on_ground && player.progression >= 1) || (climb_detection.climbing && player.progression >= 2){
+        if (on_ground && abilities.has(Ability::DoubleJump)) || on_climbable {
             player.double_jumped = false;
         }
 
         // Adjust jump height
         if input.jump_held && velocity.linvel.y > 0.0 {
-            gravity.0 = 55.0;
+            gravity.0 = tuning.jump_held_gravity;
         } else {
-            gravity.0 = 100.0;
+            gravity.0 = tuning.gravity;
         }
 
         // Fast fall
         if input.fast_fall {
-            gravity.0 = 200.0;
+            gravity.0 = tuning.fast_fall_gravity;
         }
 
-        // Climbing
-        if climb_detection.climbing && player.progression >= 2 && !ground_detection.on_ground {
-            damping.linear_damping = if input.jump_held { 0.0 } else { 15.0 };
-        } else {
-            damping.linear_damping = 0.0;
+        // Wall slide
+        if can_climb && !ground_detection.on_ground {
+            velocity.linvel.y = velocity.linvel.y.max(-tuning.wall_slide_max_speed);
         }
 
-        if input.restart && player.progression < 4 {
+        if input.restart && !player.trial_complete {
             //println!("playerposition: {}", transform.translation);
-            *transform = reset_position(transform.clone());
+            *transform = reset_position(transform.clone(), &checkpoint);
             velocity.linvel = Vec2::ZERO;
             force.force = Vec2::ZERO;
         }
@@ -212,6 +271,23 @@ pub fn player_movement(
     }
 }
 
+// Landing faster than this shakes the camera, harder the faster the fall was
+const HARD_LANDING_SPEED: f32 = 500.0;
+
+fn shake_on_hard_landing(
+    mut shake_events: EventWriter<CameraShake>,
+    query: Query<(Ref<GroundDetection>, &MovementIntent), With<Player>>,
+) {
+    for (ground_detection, intent) in query.iter() {
+        // intent.vertical still holds last frame's falling speed, player_movement hasn't run yet.
+        // The landing has to show up in the same frame, so this runs right after update_on_ground
+        if ground_detection.is_changed() && ground_detection.on_ground && intent.vertical < -HARD_LANDING_SPEED {
+            let trauma = 0.2 + (-intent.vertical - HARD_LANDING_SPEED) / 1000.0;
+            shake_events.send(CameraShake { trauma: trauma.min(0.6) });
+        }
+    }
+}
+
 fn calc_force_diff(input: f32, current_velocity: f32, target_velocity: f32) -> f32 {
     let target_speed = target_velocity * input.signum();
     let diff_to_make_up = target_speed - current_velocity;
@@ -224,18 +300,28 @@ fn update_player_animation(
     texture_atlases: Res<Assets<TextureAtlasLayout>>,
     mut animation_assets: ResMut<AnimationAssets>,
     mut query: Query<(
-        &Player,
+        &Abilities,
         &mut TextureAtlas,
         &mut Handle<Image>,
         &Velocity,
         &PlayerInput, // Added to check grappling input
+        &GroundDetection,
+        &ClimbDetection,
+        &mut Sprite,
+        Has<Dying>,
     )>,
 ) {
-    for (player, mut texture_atlas, mut texture, velocity, input) in query.iter_mut() {
+    for (abilities, mut texture_atlas, mut texture, velocity, input, ground_detection, climb_detection, mut sprite, dying) in query.iter_mut() {
         // Determine animation type based on state
-        let animation_type = if input.grapple_held && player.progression >= 3 {
+        let animation_type = if dying {
+            AnimationType::Death
+        } else if input.grapple_held && abilities.has(Ability::Grapple) {
             // If grapple is active
             AnimationType::Grapple
+        } else if climb_detection.climbing() && abilities.has(Ability::WallClimb) && !ground_detection.on_ground && velocity.linvel.y <= 0.0 {
+            // Sliding down a wall, facing it
+            sprite.flip_x = climb_detection.side == Some(WallSide::Left);
+            AnimationType::WallSlide
         } else if velocity.linvel.y.abs() > 0.1 {
             // If the player is jumping or falling
             AnimationType::Jump
@@ -258,6 +344,8 @@ fn update_player_animation(
                 texture_atlas.layout = layout_handle.clone();
                 texture_atlas.index = 0; // Start at the first frame
                 *texture = texture_handle.clone(); // Update the texture
+                // Sheets with other frame sizes are drawn at a fixed size, the rest go back to their frame size
+                sprite.custom_size = animation_assets.get_custom_size(animation_type);
                 if let Some(timer) = animation_assets.get_timer_mut(animation_type) {
                     timer.reset();
                 }
@@ -270,7 +358,12 @@ fn update_player_animation(
                     if timer.just_finished() {
                         if let Some(layout) = texture_atlases.get(&texture_atlas.layout) {
                             let texture_count = layout.textures.len();
-                            texture_atlas.index = (texture_atlas.index + 1) % texture_count;
+                            // The death animation holds its last frame instead of looping
+                            texture_atlas.index = if animation_type == AnimationType::Death {
+                                (texture_atlas.index + 1).min(texture_count - 1)
+                            } else {
+                                (texture_atlas.index + 1) % texture_count
+                            };
                         }
                     }
                 }
@@ -282,24 +375,54 @@ fn update_player_animation(
     }
 }
 
+// Level field holding how far below the top of the level, in LDtk pixels, a fall becomes fatal.
+// Levels without it kill at their bottom edge
+const KILL_PLANE_FIELD: &str = "KillPlane";
+
 fn check_fall_death(
-    mut player_query: Query<(&mut Transform, &mut Velocity), With<Player>>,
+    mut death_events: EventWriter<PlayerDied>,
+    ldtk_projects: Query<&Handle<LdtkProject>>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    player_query: Query<&Transform, (With<Player>, Without<Dying>)>,
 ) {
-    for (mut transform, mut velocity) in player_query.iter_mut() {
-        //print!("playerposition: {}", transform.translation);
-        // Adjust this value based on your lowest platform/level position
-        const DEATH_Y_THRESHOLD: f32 = -1257.0; // or whatever value works for your map
-
-        if transform.translation.y < DEATH_Y_THRESHOLD {
-            // Use your existing reset_position function
-            *transform = reset_position(transform.clone());
-            velocity.linvel = Vec2::ZERO;
+    let project = if let Some(project) = ldtk_projects.get_single().ok().and_then(|handle| ldtk_project_assets.get(handle)) {
+        project
+    } else {
+        return;
+    };
+
+    for transform in player_query.iter() {
+        let position = transform.translation.truncate();
+        // Falling out of one level into the one below it is fine, the player only dies
+        // once they're below the kill plane of every level they're over
+        let levels = &project.json_data().levels;
+        let lowest_kill_plane = levels
+            .iter()
+            .filter(|level| {
+                let left = level.world_x as f32;
+                position.x >= left && position.x < left + level.px_wid as f32
+            })
+            .map(kill_plane_y)
+            .reduce(f32::min)
+            // Out past the sides of every level there's nothing to land on, use the lowest kill plane in the world
+            .or_else(|| levels.iter().map(kill_plane_y).reduce(f32::min));
+
+        if let Some(kill_plane) = lowest_kill_plane {
+            if position.y < kill_plane {
+                death_events.send(PlayerDied { cause: DeathCause::Fall });
+            }
         }
     }
 }
 
-pub fn reset_position(mut transform: Transform) -> Transform {
-    transform.translation = Vec3::new(463.0, -344.0, 10.0);
+// World y of a level's kill plane, LDtk's y axis points down
+fn kill_plane_y(level: &bevy_ecs_ldtk::ldtk::Level) -> f32 {
+    let depth = level.get_int_field(KILL_PLANE_FIELD).map_or(level.px_hei, |depth| *depth);
+    -(level.world_y + depth) as f32
+}
+
+pub fn reset_position(mut transform: Transform, checkpoint: &CurrentCheckpoint) -> Transform {
+    transform.translation = checkpoint.position.extend(10.0);
     transform
 }
 
@@ -309,6 +432,6 @@ impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app
             .register_ldtk_entity::<PlayerBundle>("Player")
-            .add_systems(Update, (check_fall_death,player_input, player_movement.after(player_input), update_player_animation.after(player_movement), camera_follow_system,));
+            .add_systems(Update, (check_fall_death,player_input, shake_on_hard_landing.after(update_on_ground).before(player_movement), player_movement.after(player_input), update_player_animation.after(player_movement),).run_if(in_state(GameState::Game)));
     }
 }
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::death::DeathCounter;
 use crate::game_menu::GameState;
 use crate::player::Player;
 
@@ -12,7 +13,7 @@ pub fn toggle_progression_ui(
     game_state: Res<State<GameState>>,
     mut query: Query<(&mut Style, &mut ProgressionVisible)>,
 ) {
-    let is_game = *game_state == GameState::Loading;
+    let is_game = matches!(game_state.get(), GameState::Game | GameState::Paused);
 
     for (mut style, mut visible) in query.iter_mut() {
         visible.0 = is_game;
@@ -33,7 +34,7 @@ pub fn setup_progression_ui(mut commands: Commands, asset_server: Res<AssetServe
 
     commands.spawn((
         TextBundle::from_section(
-            "Progression: 0",
+            "Progression: 0\nDeaths: 0",
             TextStyle {
                 font,
                 font_size: 40.0,
@@ -53,12 +54,16 @@ pub fn setup_progression_ui(mut commands: Commands, asset_server: Res<AssetServe
 }
 
 pub fn update_progression_ui(
-    player_query: Query<&Player, Changed<Player>>,
+    death_counter: Res<DeathCounter>,
+    player_query: Query<Ref<Player>>,
     mut query: Query<&mut Text, With<ProgressionText>>,
 ) {
     if let Ok(player) = player_query.get_single() {
+        if !player.is_changed() && !death_counter.is_changed() {
+            return;
+        }
         for mut text in query.iter_mut() {
-            text.sections[0].value = format!("Progression: {}", player.progression);
+            text.sections[0].value = format!("Progression: {}\nDeaths: {}", player.progression, death_counter.deaths);
         }
     }
 }
@@ -0,0 +1,173 @@
+//save.rs
+use crate::abilities::{Abilities, Ability};
+use crate::checkpoints::CurrentCheckpoint;
+use crate::collectibles::CollectedArtifacts;
+use crate::death::DeathCounter;
+use crate::game_menu::GameState;
+use crate::player::{reset_position, Player};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+const SAVE_DIR_NAME: &str = "path-of-artifacts";
+const SAVE_FILE_NAME: &str = "save.ron";
+
+//everything needed to pick up where the player left off, written to the user's data directory
+#[derive(Clone, Default, Debug, Serialize, Deserialize)]
+pub struct SaveData {
+    pub progression: u8,
+    pub abilities: Vec<Ability>,
+    pub trial_complete: bool,
+    pub collected: Vec<String>,
+    pub checkpoint_iid: Option<String>,
+    pub checkpoint_position: [f32; 2],
+    pub play_time: f64,
+    // Missing from saves written before deaths were counted
+    #[serde(default)]
+    pub deaths: u32,
+}
+
+// Sent whenever progress worth keeping happens (collectible picked up, checkpoint reached)
+#[derive(Event)]
+pub struct SaveGame;
+
+// Save loaded from the "Continue" button, applied once the player has spawned
+#[derive(Resource)]
+pub struct PendingSave(pub SaveData);
+
+#[derive(Resource, Default)]
+pub struct PlayTime {
+    pub seconds: f64,
+}
+
+// Per-user directory for the save and config files
+pub fn data_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(SAVE_DIR_NAME)
+}
+
+pub fn save_path() -> PathBuf {
+    data_dir().join(SAVE_FILE_NAME)
+}
+
+pub fn save_exists() -> bool {
+    save_path().exists()
+}
+
+pub fn load_save() -> Option<SaveData> {
+    let contents = fs::read_to_string(save_path()).ok()?;
+    match ron::from_str(&contents) {
+        Ok(save) => Some(save),
+        Err(err) => {
+            warn!("Could not read save file: {}", err);
+            None
+        }
+    }
+}
+
+fn write_save_file(save: &SaveData) {
+    let path = save_path();
+    if let Some(dir) = path.parent() {
+        if let Err(err) = fs::create_dir_all(dir) {
+            warn!("Could not create save directory {:?}: {}", dir, err);
+            return;
+        }
+    }
+    match ron::ser::to_string_pretty(save, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => {
+            if let Err(err) = fs::write(&path, contents) {
+                warn!("Could not write save file {:?}: {}", path, err);
+            }
+        }
+        Err(err) => warn!("Could not serialize save: {}", err),
+    }
+}
+
+fn tick_play_time(time: Res<Time>, mut play_time: ResMut<PlayTime>) {
+    play_time.seconds += time.delta_seconds_f64();
+}
+
+// Writes the save on request and when the game is closed mid-run
+fn write_save(
+    mut save_events: EventReader<SaveGame>,
+    mut exit_events: EventReader<AppExit>,
+    game_state: Res<State<GameState>>,
+    pending_save: Option<Res<PendingSave>>,
+    current_checkpoint: Res<CurrentCheckpoint>,
+    collected: Res<CollectedArtifacts>,
+    play_time: Res<PlayTime>,
+    death_counter: Res<DeathCounter>,
+    player_query: Query<(&Player, &Abilities)>,
+) {
+    let requested = save_events.read().count() > 0;
+    let exiting = exit_events.read().count() > 0;
+    if !requested && !exiting {
+        return;
+    }
+    // Nothing has been played yet, or the loaded save hasn't been applied
+    if *game_state.get() == GameState::MainMenu || pending_save.is_some() {
+        return;
+    }
+
+    if let Ok((player, abilities)) = player_query.get_single() {
+        write_save_file(&SaveData {
+            progression: player.progression,
+            abilities: abilities.iter().collect(),
+            trial_complete: player.trial_complete,
+            collected: collected.iids.iter().cloned().collect(),
+            checkpoint_iid: current_checkpoint.iid.clone(),
+            checkpoint_position: current_checkpoint.position.to_array(),
+            play_time: play_time.seconds,
+            deaths: death_counter.deaths,
+        });
+    }
+}
+
+fn apply_pending_save(
+    mut commands: Commands,
+    pending_save: Res<PendingSave>,
+    mut current_checkpoint: ResMut<CurrentCheckpoint>,
+    mut collected: ResMut<CollectedArtifacts>,
+    mut play_time: ResMut<PlayTime>,
+    mut death_counter: ResMut<DeathCounter>,
+    mut player_query: Query<(&mut Player, &mut Abilities, &mut Transform, &mut Velocity)>,
+) {
+    let (mut player, mut abilities, mut transform, mut velocity) = if let Ok(player) = player_query.get_single_mut() {
+        player
+    } else {
+        return;
+    };
+    let save = &pending_save.0;
+
+    player.progression = save.progression;
+    player.trial_complete = save.trial_complete;
+    for ability in &save.abilities {
+        abilities.grant(*ability);
+    }
+    collected.iids = save.collected.iter().cloned().collect();
+    play_time.seconds = save.play_time;
+    death_counter.deaths = save.deaths;
+
+    current_checkpoint.iid = save.checkpoint_iid.clone();
+    current_checkpoint.position = Vec2::from(save.checkpoint_position);
+    *transform = reset_position(transform.clone(), &current_checkpoint);
+    velocity.linvel = Vec2::ZERO;
+
+    commands.remove_resource::<PendingSave>();
+}
+
+pub struct SavePlugin;
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SaveGame>()
+            .init_resource::<PlayTime>()
+            .add_systems(Update, (
+                tick_play_time.run_if(in_state(GameState::Game)),
+                apply_pending_save.run_if(resource_exists::<PendingSave>),
+            ))
+            .add_systems(Last, write_save);
+    }
+}
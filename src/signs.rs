@@ -0,0 +1,108 @@
+//signs.rs
+use crate::game_menu::GameState;
+use crate::player::Player;
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+const SIGN_FONT_SIZE: f32 = 25.0;
+// Alpha per second for signs fading in and out around their trigger radius
+const SIGN_FADE_RATE: f32 = 4.0;
+
+#[derive(Clone, Default, Bundle, LdtkEntity)]
+pub struct SignBundle {
+    #[from_entity_instance]
+    sign: Sign,
+    visibility: VisibilityBundle,
+}
+
+// Text placed in the map, spawned and despawned with its level.
+// Signs with a trigger radius only show while the player is that close
+#[derive(Clone, Component, Default)]
+pub struct Sign {
+    text: String,
+    font_size: f32,
+    trigger_radius: Option<f32>,
+}
+
+impl From<&EntityInstance> for Sign {
+    fn from(entity_instance: &EntityInstance) -> Self {
+        let text = entity_instance
+            .get_maybe_string_field("Text")
+            .ok()
+            .and_then(|text| text.clone())
+            .unwrap_or_default();
+
+        Sign {
+            text,
+            font_size: entity_instance.get_int_field("FontSize").map_or(SIGN_FONT_SIZE, |size| *size as f32),
+            trigger_radius: entity_instance.get_float_field("TriggerRadius").ok().copied(),
+        }
+    }
+}
+
+#[derive(Component)]
+struct SignText;
+
+fn spawn_sign_text(mut commands: Commands, asset_server: Res<AssetServer>, sign_query: Query<(Entity, &Sign), Added<Sign>>) {
+    for (sign_entity, sign) in sign_query.iter() {
+        // Triggered signs start hidden and fade in once the player comes close
+        let alpha = if sign.trigger_radius.is_some() { 0.0 } else { 1.0 };
+        commands.entity(sign_entity).with_children(|parent| {
+            parent.spawn((
+                Text2dBundle {
+                    text: Text::from_section(
+                        sign.text.clone(),
+                        TextStyle {
+                            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                            font_size: sign.font_size,
+                            color: Color::WHITE.with_alpha(alpha),
+                        },
+                    ).with_justify(JustifyText::Center),
+                    transform: Transform::from_xyz(0.0, 0.0, 5.0),
+                    ..default()
+                },
+                SignText,
+            ));
+        });
+    }
+}
+
+fn fade_triggered_signs(
+    time: Res<Time>,
+    player_query: Query<&GlobalTransform, With<Player>>,
+    sign_query: Query<(&Sign, &GlobalTransform, &Children)>,
+    mut text_query: Query<&mut Text, With<SignText>>,
+) {
+    let player_position = if let Ok(player_transform) = player_query.get_single() {
+        player_transform.translation().truncate()
+    } else {
+        return;
+    };
+
+    for (sign, sign_transform, children) in sign_query.iter() {
+        let trigger_radius = if let Some(trigger_radius) = sign.trigger_radius {
+            trigger_radius
+        } else {
+            continue;
+        };
+        let in_range = sign_transform.translation().truncate().distance(player_position) <= trigger_radius;
+        let step = SIGN_FADE_RATE * time.delta_seconds() * if in_range { 1.0 } else { -1.0 };
+
+        let mut text_iter = text_query.iter_many_mut(children);
+        while let Some(mut text) = text_iter.fetch_next() {
+            for section in text.sections.iter_mut() {
+                let alpha = (section.style.color.alpha() + step).clamp(0.0, 1.0);
+                section.style.color.set_alpha(alpha);
+            }
+        }
+    }
+}
+
+pub struct SignPlugin;
+impl Plugin for SignPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_ldtk_entity::<SignBundle>("Sign")
+            .add_systems(Update, spawn_sign_text)
+            .add_systems(Update, fade_triggered_signs.run_if(in_state(GameState::Game)));
+    }
+}
@@ -0,0 +1,342 @@
+//sound.rs
+use crate::game_menu::GameState;
+use crate::save::data_dir;
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+use bevy_kira_audio::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+const SETTINGS_FILE_NAME: &str = "settings.ron";
+
+// Separate kira channels so each has its own volume, and stopping the music doesn't cut off effects
+#[derive(Resource)]
+pub struct MusicChannel;
+
+#[derive(Resource)]
+pub struct SfxChannel;
+
+#[derive(Resource)]
+pub struct UiChannel;
+
+//volume levels from 0 to 1, persisted to settings.ron next to the save file
+#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        AudioSettings {
+            master: 1.0,
+            music: 1.0,
+            sfx: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VolumeSetting {
+    Master,
+    Music,
+    Sfx,
+}
+
+impl VolumeSetting {
+    pub const ALL: [VolumeSetting; 3] = [VolumeSetting::Master, VolumeSetting::Music, VolumeSetting::Sfx];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            VolumeSetting::Master => "Master",
+            VolumeSetting::Music => "Music",
+            VolumeSetting::Sfx => "Effects",
+        }
+    }
+}
+
+impl AudioSettings {
+    pub fn volume(&self, setting: VolumeSetting) -> f32 {
+        match setting {
+            VolumeSetting::Master => self.master,
+            VolumeSetting::Music => self.music,
+            VolumeSetting::Sfx => self.sfx,
+        }
+    }
+
+    pub fn set_volume(&mut self, setting: VolumeSetting, volume: f32) {
+        let volume = volume.clamp(0.0, 1.0);
+        match setting {
+            VolumeSetting::Master => self.master = volume,
+            VolumeSetting::Music => self.music = volume,
+            VolumeSetting::Sfx => self.sfx = volume,
+        }
+    }
+}
+
+fn load_audio_settings() -> AudioSettings {
+    fs::read_to_string(data_dir().join(SETTINGS_FILE_NAME))
+        .ok()
+        .and_then(|contents| match ron::from_str(&contents) {
+            Ok(settings) => Some(settings),
+            Err(err) => {
+                warn!("Could not read settings file, using defaults: {}", err);
+                None
+            }
+        })
+        .unwrap_or_default()
+}
+
+fn write_audio_settings(settings: &AudioSettings) {
+    let dir = data_dir();
+    if let Err(err) = fs::create_dir_all(&dir) {
+        warn!("Could not create config directory {:?}: {}", dir, err);
+        return;
+    }
+    match ron::ser::to_string_pretty(settings, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => {
+            if let Err(err) = fs::write(dir.join(SETTINGS_FILE_NAME), contents) {
+                warn!("Could not write settings file: {}", err);
+            }
+        }
+        Err(err) => warn!("Could not serialize settings: {}", err),
+    }
+}
+
+// UI sounds count as effects for the sliders
+fn apply_audio_settings(
+    settings: Res<AudioSettings>,
+    music: Res<AudioChannel<MusicChannel>>,
+    sfx: Res<AudioChannel<SfxChannel>>,
+    ui: Res<AudioChannel<UiChannel>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    music.set_volume((settings.master * settings.music) as f64);
+    sfx.set_volume((settings.master * settings.sfx) as f64);
+    ui.set_volume((settings.master * settings.sfx) as f64);
+}
+
+//settings screen
+
+#[derive(Component)]
+pub struct SettingsPopup;
+
+#[derive(Component)]
+pub struct VolumeSlider(VolumeSetting);
+
+#[derive(Component)]
+pub struct VolumeFill(VolumeSetting);
+
+#[derive(Component)]
+pub struct VolumeText(VolumeSetting);
+
+#[derive(Component)]
+pub struct CloseSettingsButton;
+
+fn volume_label(settings: &AudioSettings, setting: VolumeSetting) -> String {
+    format!("{:.0}%", settings.volume(setting) * 100.0)
+}
+
+pub fn show_settings_popup(commands: &mut Commands, asset_server: &AssetServer, settings: &AudioSettings) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+
+    commands.spawn((
+        // Spawn a container for both the background and the popup
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.7).into(), // Dimmed background
+            ..Default::default()
+        },
+        SettingsPopup,
+    ))
+        .with_children(|parent| {
+            // Popup container
+            parent.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(500.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(20.0)),
+                    flex_direction: FlexDirection::Column,
+                    ..Default::default()
+                },
+                background_color: Color::srgb(0.2, 0.2, 0.3).into(), // Dark blue-gray
+                ..Default::default()
+            })
+                .with_children(|popup| {
+                    popup.spawn(TextBundle::from_section(
+                        "Settings - click or drag a bar to set its volume",
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 22.0,
+                            color: Color::WHITE,
+                        },
+                    ).with_style(Style {
+                        margin: UiRect::all(Val::Px(10.0)),
+                        ..Default::default()
+                    }));
+
+                    for setting in VolumeSetting::ALL {
+                        popup.spawn(NodeBundle {
+                            style: Style {
+                                width: Val::Percent(100.0),
+                                justify_content: JustifyContent::SpaceBetween,
+                                align_items: AlignItems::Center,
+                                margin: UiRect::all(Val::Px(4.0)),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        })
+                            .with_children(|row| {
+                                row.spawn(TextBundle::from_section(
+                                    setting.label(),
+                                    TextStyle {
+                                        font: font.clone(),
+                                        font_size: 20.0,
+                                        color: Color::WHITE,
+                                    },
+                                ));
+                                // The bar is a button so dragging keeps it pressed, its fill shows the volume
+                                row.spawn((
+                                    ButtonBundle {
+                                        style: Style {
+                                            width: Val::Px(260.0),
+                                            height: Val::Px(24.0),
+                                            ..Default::default()
+                                        },
+                                        background_color: Color::srgb(0.9, 0.9, 0.9).into(),
+                                        ..Default::default()
+                                    },
+                                    RelativeCursorPosition::default(),
+                                    VolumeSlider(setting),
+                                ))
+                                    .with_children(|slider| {
+                                        slider.spawn((
+                                            NodeBundle {
+                                                style: Style {
+                                                    width: Val::Percent(settings.volume(setting) * 100.0),
+                                                    height: Val::Percent(100.0),
+                                                    ..Default::default()
+                                                },
+                                                background_color: Color::srgb(0.3, 0.6, 0.9).into(),
+                                                ..Default::default()
+                                            },
+                                            VolumeFill(setting),
+                                        ));
+                                    });
+                                row.spawn((
+                                    TextBundle::from_section(
+                                        volume_label(settings, setting),
+                                        TextStyle {
+                                            font: font.clone(),
+                                            font_size: 20.0,
+                                            color: Color::WHITE,
+                                        },
+                                    ).with_style(Style {
+                                        width: Val::Px(60.0),
+                                        ..Default::default()
+                                    }),
+                                    VolumeText(setting),
+                                ));
+                            });
+                    }
+
+                    // Close button
+                    popup.spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(120.0),
+                                height: Val::Px(40.0),
+                                margin: UiRect::all(Val::Px(10.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..Default::default()
+                            },
+                            background_color: Color::srgb(0.8, 0.0, 0.0).into(), // Bright red
+                            ..Default::default()
+                        },
+                        CloseSettingsButton,
+                    ))
+                        .with_children(|button| {
+                            button.spawn(TextBundle::from_section(
+                                "Close",
+                                TextStyle {
+                                    font: font.clone(),
+                                    font_size: 20.0,
+                                    color: Color::WHITE,
+                                },
+                            ));
+                        });
+                });
+        });
+}
+
+fn settings_popup_interaction(
+    mut commands: Commands,
+    mut settings: ResMut<AudioSettings>,
+    slider_query: Query<(&Interaction, &RelativeCursorPosition, &VolumeSlider)>,
+    close_query: Query<&Interaction, (Changed<Interaction>, With<CloseSettingsButton>)>,
+    popup_query: Query<Entity, With<SettingsPopup>>,
+) {
+    for (interaction, cursor, slider) in slider_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        if let Some(position) = cursor.normalized {
+            let volume = position.x.clamp(0.0, 1.0);
+            if settings.volume(slider.0) != volume {
+                settings.set_volume(slider.0, volume);
+            }
+        }
+    }
+
+    // Written once on close rather than on every frame of a drag
+    if close_query.iter().any(|interaction| *interaction == Interaction::Pressed) {
+        write_audio_settings(&settings);
+        for popup_entity in popup_query.iter() {
+            commands.entity(popup_entity).despawn_recursive();
+        }
+    }
+}
+
+fn update_volume_sliders(
+    settings: Res<AudioSettings>,
+    mut fill_query: Query<(&mut Style, &VolumeFill)>,
+    mut text_query: Query<(&mut Text, &VolumeText)>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    for (mut style, fill) in fill_query.iter_mut() {
+        style.width = Val::Percent(settings.volume(fill.0) * 100.0);
+    }
+    for (mut text, volume_text) in text_query.iter_mut() {
+        text.sections[0].value = volume_label(&settings, volume_text.0);
+    }
+}
+
+pub struct SoundPlugin;
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_audio_channel::<MusicChannel>()
+            .add_audio_channel::<SfxChannel>()
+            .add_audio_channel::<UiChannel>()
+            .insert_resource(load_audio_settings())
+            .add_systems(Update, apply_audio_settings)
+            .add_systems(Update, (
+                settings_popup_interaction,
+                update_volume_sliders.after(settings_popup_interaction),
+            ).run_if(in_state(GameState::MainMenu)));
+    }
+}
@@ -0,0 +1,184 @@
+//tuning.rs
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::fmt;
+
+const TUNING_PATH: &str = "movement.tuning.ron";
+
+//movement feel values, read from assets/movement.tuning.ron and reloaded whenever the file is saved.
+//the asset is mirrored into a resource of the same type so systems can just use Res<MovementTuning>
+#[derive(Asset, Resource, TypePath, Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct MovementTuning {
+    pub player: PlayerTuning,
+    pub grapple: GrappleTuning,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct PlayerTuning {
+    pub acceleration_multiplier: f32,
+    pub top_speed: f32,
+    pub jump_strength: f32,
+    // Gravity scales: normal, while rising with jump held, and while fast falling
+    pub gravity: f32,
+    pub jump_held_gravity: f32,
+    pub fast_fall_gravity: f32,
+    pub wall_jump_kick: f32,
+    pub wall_jump_strength: f32,
+    pub wall_jump_input_lock: f32,
+    pub wall_slide_max_speed: f32,
+    // Grace windows in seconds for jumping after leaving a ledge or wall, and for pressing jump early
+    pub coyote_time: f32,
+    pub jump_buffer: f32,
+    // The ground and climb sensors lag a frame or two behind takeoff, so they can't refill the windows this long after a jump
+    pub jump_refill_lock: f32,
+}
+
+impl Default for PlayerTuning {
+    fn default() -> Self {
+        Self {
+            acceleration_multiplier: 400.0,
+            top_speed: 250.0,
+            jump_strength: 270.0,
+            gravity: 100.0,
+            jump_held_gravity: 55.0,
+            fast_fall_gravity: 200.0,
+            wall_jump_kick: 260.0,
+            wall_jump_strength: 260.0,
+            wall_jump_input_lock: 0.15,
+            wall_slide_max_speed: 80.0,
+            coyote_time: 0.1,
+            jump_buffer: 0.12,
+            jump_refill_lock: 0.1,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct GrappleTuning {
+    // Hook speeds along the aim, chosen so the default diagonal matches the old 300/700 per axis
+    pub launch_speed: f32,
+    pub hook_speed: f32,
+    // A hook that flies further than this without latching misses and comes back
+    pub max_range: f32,
+    pub retract_speed: f32,
+    pub cooldown: f32,
+    pub rope_min_length: f32,
+    pub rope_max_length: f32,
+    // How fast up/down reel the rope in and out while swinging
+    pub reel_speed: f32,
+    // Holding left/right below the anchor pumps the swing, letting go fast enough along it adds a boost
+    pub swing_pump_acceleration: f32,
+    pub swing_max_speed: f32,
+    pub swing_release_min_speed: f32,
+    pub swing_release_boost: f32,
+    // Pull anchors reel the player in at pull_speed and let go with pull_exit_speed
+    pub pull_speed: f32,
+    pub pull_exit_speed: f32,
+    pub zipline_speed: f32,
+}
+
+impl Default for GrappleTuning {
+    fn default() -> Self {
+        Self {
+            launch_speed: 424.0,
+            hook_speed: 990.0,
+            max_range: 360.0,
+            retract_speed: 1200.0,
+            cooldown: 0.35,
+            rope_min_length: 24.0,
+            rope_max_length: 320.0,
+            reel_speed: 150.0,
+            swing_pump_acceleration: 600.0,
+            swing_max_speed: 900.0,
+            swing_release_min_speed: 150.0,
+            swing_release_boost: 180.0,
+            pull_speed: 700.0,
+            pull_exit_speed: 250.0,
+            zipline_speed: 380.0,
+        }
+    }
+}
+
+#[derive(Resource)]
+struct MovementTuningHandle(Handle<MovementTuning>);
+
+#[derive(Default)]
+struct MovementTuningLoader;
+
+#[derive(Debug)]
+pub enum MovementTuningLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for MovementTuningLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MovementTuningLoaderError::Io(err) => write!(f, "could not read movement tuning: {}", err),
+            MovementTuningLoaderError::Ron(err) => write!(f, "could not parse movement tuning: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for MovementTuningLoaderError {}
+
+impl AssetLoader for MovementTuningLoader {
+    type Asset = MovementTuning;
+    type Settings = ();
+    type Error = MovementTuningLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<MovementTuning, MovementTuningLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await.map_err(MovementTuningLoaderError::Io)?;
+        ron::de::from_bytes(&bytes).map_err(MovementTuningLoaderError::Ron)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["tuning.ron"]
+    }
+}
+
+fn load_movement_tuning(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(MovementTuningHandle(asset_server.load(TUNING_PATH)));
+}
+
+// Copies the file into the resource when it first loads and every time it's edited
+fn apply_movement_tuning(
+    mut commands: Commands,
+    mut asset_events: EventReader<AssetEvent<MovementTuning>>,
+    tuning_assets: Res<Assets<MovementTuning>>,
+    handle: Res<MovementTuningHandle>,
+) {
+    for event in asset_events.read() {
+        match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } if *id == handle.0.id() => {
+                if let Some(tuning) = tuning_assets.get(*id) {
+                    info!("Applied movement tuning from {}", TUNING_PATH);
+                    commands.insert_resource(tuning.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+pub struct TuningPlugin;
+impl Plugin for TuningPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<MovementTuning>()
+            .init_asset_loader::<MovementTuningLoader>()
+            .init_resource::<MovementTuning>()
+            .add_systems(Startup, load_movement_tuning)
+            .add_systems(Update, apply_movement_tuning.run_if(resource_exists::<MovementTuningHandle>));
+    }
+}
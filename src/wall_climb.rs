@@ -1,17 +1,42 @@
 use bevy::prelude::*;
+use bevy::utils::HashMap;
 use bevy_rapier2d::prelude::*;
 use std::collections::HashSet;
 
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum WallSide {
+    Left,
+    Right,
+}
+
+impl WallSide {
+    // -1 for a wall on the left, 1 for a wall on the right
+    pub fn direction(&self) -> f32 {
+        match self {
+            WallSide::Left => -1.0,
+            WallSide::Right => 1.0,
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct ClimbSensor {
     pub climb_detection_entity: Entity,
+    pub side: WallSide,
     pub intersecting_climbables: HashSet<Entity>,
 }
 
+// Which wall the entity is hugging, if any
 #[derive(Clone, Default, Component)]
 pub struct ClimbDetection {
-    pub climbing: bool,
+    pub side: Option<WallSide>,
+}
+
+impl ClimbDetection {
+    pub fn climbing(&self) -> bool {
+        self.side.is_some()
+    }
 }
 
 pub fn spawn_climb_sensor(
@@ -22,23 +47,28 @@ pub fn spawn_climb_sensor(
         if let Some(cuboid) = shape.as_cuboid() {
             let Vec2 {
                 x: half_extents_x,
-                //y: half_extents_y,
-                ..
+                y: half_extents_y,
             } = cuboid.half_extents();
 
-            let detector_shape = Collider::cuboid(half_extents_x/1.5, 1.0);
+            // Thin strips just outside each side, shorter than the body so floors and ceilings don't count
+            let detector_shape = Collider::cuboid(1.5, half_extents_y / 1.5);
 
             commands.entity(entity).with_children(|builder| {
-                builder
-                    .spawn_empty()
-                    .insert(ActiveEvents::COLLISION_EVENTS)
-                    .insert(detector_shape)
-                    .insert(Sensor)
-                    .insert(GlobalTransform::default())
-                    .insert(ClimbSensor {
-                        climb_detection_entity: entity,
-                        intersecting_climbables: HashSet::new(),
-                    });
+                for side in [WallSide::Left, WallSide::Right] {
+                    let sensor_translation = Vec3::new(side.direction() * (half_extents_x + 1.0), 0., 0.);
+                    builder
+                        .spawn_empty()
+                        .insert(ActiveEvents::COLLISION_EVENTS)
+                        .insert(detector_shape.clone())
+                        .insert(Sensor)
+                        .insert(Transform::from_translation(sensor_translation))
+                        .insert(GlobalTransform::default())
+                        .insert(ClimbSensor {
+                            climb_detection_entity: entity,
+                            side,
+                            intersecting_climbables: HashSet::new(),
+                        });
+                }
             });
         }
     }
@@ -80,11 +110,32 @@ pub fn climb_detection(
 
 pub fn update_climbing(
     mut climb_detectors: Query<&mut ClimbDetection>,
-    climb_sensors: Query<&ClimbSensor, Changed<ClimbSensor>>,
+    climb_sensors: Query<&ClimbSensor>,
 ) {
+    // (left, right) contact for every entity with climb sensors
+    let mut touching: HashMap<Entity, (bool, bool)> = HashMap::new();
     for sensor in &climb_sensors {
-        if let Ok(mut climb_detection) = climb_detectors.get_mut(sensor.climb_detection_entity) {
-            climb_detection.climbing = !sensor.intersecting_climbables.is_empty();
+        let contact = touching.entry(sensor.climb_detection_entity).or_default();
+        let hit = !sensor.intersecting_climbables.is_empty();
+        match sensor.side {
+            WallSide::Left => contact.0 |= hit,
+            WallSide::Right => contact.1 |= hit,
+        }
+    }
+
+    for (entity, (left, right)) in touching {
+        if let Ok(mut climb_detection) = climb_detectors.get_mut(entity) {
+            let side = match (left, right) {
+                (true, false) => Some(WallSide::Left),
+                (false, true) => Some(WallSide::Right),
+                // Squeezed between two walls, stay on the one already held
+                (true, true) => climb_detection.side.or(Some(WallSide::Left)),
+                (false, false) => None,
+            };
+            // Only write on change so change detection stays meaningful
+            if climb_detection.side != side {
+                climb_detection.side = side;
+            }
         }
     }
 }
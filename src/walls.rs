@@ -1,3 +1,4 @@
+use crate::grapple::Hookable;
 use bevy::{prelude::*, utils::HashSet, utils::HashMap};
 use bevy_ecs_ldtk::prelude::*;
 use bevy_rapier2d::prelude::*;
@@ -10,6 +11,13 @@ pub struct WallBundle {
     wall: Wall,
 }
 
+// Wall tiles the grapple hook can latch on to
+#[derive(Clone, Debug, Default, Bundle, LdtkIntCell)]
+pub struct HookWallBundle {
+    wall: Wall,
+    hookable: Hookable,
+}
+
 
 /// Spawns heron collisions for the walls of a level
 ///
@@ -27,9 +35,11 @@ pub struct WallBundle {
 /// 2. combine wall tiles into flat "plates" in each individual row
 /// 3. combine the plates into rectangles across multiple rows wherever possible
 /// 4. spawn colliders for each rectangle
+///
+/// Hookable walls are merged separately so their colliders can be told apart by the grapple.
 pub fn spawn_wall_collisions(
     mut commands: Commands,
-    wall_query: Query<(&GridCoords, &Parent), Added<Wall>>,
+    wall_query: Query<(&GridCoords, &Parent, Has<Hookable>), Added<Wall>>,
     parent_query: Query<&Parent, Without<Wall>>,
     level_query: Query<(Entity, &LevelIid)>,
     ldtk_projects: Query<&Handle<LdtkProject>>,
@@ -58,15 +68,16 @@ pub fn spawn_wall_collisions(
     // This has two consequences in the resulting collision entities:
     // 1. it forces the walls to be split along level boundaries
     // 2. it lets us easily add the collision entities as children of the appropriate level entity
-    let mut level_to_wall_locations: HashMap<Entity, HashSet<GridCoords>> = HashMap::new();
+    // Hookable walls get their own set per level so they never merge with plain walls.
+    let mut level_to_wall_locations: HashMap<(Entity, bool), HashSet<GridCoords>> = HashMap::new();
 
-    wall_query.iter().for_each(|(&grid_coords, parent)| {
+    wall_query.iter().for_each(|(&grid_coords, parent, hookable)| {
         // An intgrid tile's direct parent will be a layer entity, not the level entity
         // To get the level entity, you need the tile's grandparent.
         // This is where parent_query comes in.
         if let Ok(grandparent) = parent_query.get(parent.get()) {
             level_to_wall_locations
-                .entry(grandparent.get())
+                .entry((grandparent.get(), hookable))
                 .or_default()
                 .insert(grid_coords);
         }
@@ -74,104 +85,109 @@ pub fn spawn_wall_collisions(
 
     if !wall_query.is_empty() {
         level_query.iter().for_each(|(level_entity, level_iid)| {
-            if let Some(level_walls) = level_to_wall_locations.get(&level_entity) {
-                let ldtk_project = ldtk_project_assets
-                    .get(ldtk_projects.single())
-                    .expect("Project should be loaded if level has spawned");
-
-                let level = ldtk_project
-                    .as_standalone()
-                    .get_loaded_level_by_iid(&level_iid.to_string())
-                    .expect("Spawned level should exist in LDtk project");
-
-                let LayerInstance {
-                    c_wid: width,
-                    c_hei: height,
-                    grid_size,
-                    ..
-                } = level.layer_instances()[0];
-
-                // combine wall tiles into flat "plates" in each individual row
-                let mut plate_stack: Vec<Vec<Plate>> = Vec::new();
-
-                for y in 0..height {
-                    let mut row_plates: Vec<Plate> = Vec::new();
-                    let mut plate_start = None;
-
-                    // + 1 to the width so the algorithm "terminates" plates that touch the right edge
-                    for x in 0..width + 1 {
-                        match (plate_start, level_walls.contains(&GridCoords { x, y })) {
-                            (Some(s), false) => {
-                                row_plates.push(Plate {
-                                    left: s,
-                                    right: x - 1,
-                                });
-                                plate_start = None;
+            for hookable in [false, true] {
+                if let Some(level_walls) = level_to_wall_locations.get(&(level_entity, hookable)) {
+                    let ldtk_project = ldtk_project_assets
+                        .get(ldtk_projects.single())
+                        .expect("Project should be loaded if level has spawned");
+
+                    let level = ldtk_project
+                        .as_standalone()
+                        .get_loaded_level_by_iid(&level_iid.to_string())
+                        .expect("Spawned level should exist in LDtk project");
+
+                    let LayerInstance {
+                        c_wid: width,
+                        c_hei: height,
+                        grid_size,
+                        ..
+                    } = level.layer_instances()[0];
+
+                    // combine wall tiles into flat "plates" in each individual row
+                    let mut plate_stack: Vec<Vec<Plate>> = Vec::new();
+
+                    for y in 0..height {
+                        let mut row_plates: Vec<Plate> = Vec::new();
+                        let mut plate_start = None;
+
+                        // + 1 to the width so the algorithm "terminates" plates that touch the right edge
+                        for x in 0..width + 1 {
+                            match (plate_start, level_walls.contains(&GridCoords { x, y })) {
+                                (Some(s), false) => {
+                                    row_plates.push(Plate {
+                                        left: s,
+                                        right: x - 1,
+                                    });
+                                    plate_start = None;
+                                }
+                                (None, true) => plate_start = Some(x),
+                                _ => (),
                             }
-                            (None, true) => plate_start = Some(x),
-                            _ => (),
                         }
-                    }
-
-                    plate_stack.push(row_plates);
-                }
-
-                // combine "plates" into rectangles across multiple rows
-                let mut rect_builder: HashMap<Plate, Rect> = HashMap::new();
-                let mut prev_row: Vec<Plate> = Vec::new();
-                let mut wall_rects: Vec<Rect> = Vec::new();
 
-                // an extra empty row so the algorithm "finishes" the rects that touch the top edge
-                plate_stack.push(Vec::new());
+                        plate_stack.push(row_plates);
+                    }
 
-                for (y, current_row) in plate_stack.into_iter().enumerate() {
-                    for prev_plate in &prev_row {
-                        if !current_row.contains(prev_plate) {
-                            // remove the finished rect so that the same plate in the future starts a new rect
-                            if let Some(rect) = rect_builder.remove(prev_plate) {
-                                wall_rects.push(rect);
+                    // combine "plates" into rectangles across multiple rows
+                    let mut rect_builder: HashMap<Plate, Rect> = HashMap::new();
+                    let mut prev_row: Vec<Plate> = Vec::new();
+                    let mut wall_rects: Vec<Rect> = Vec::new();
+
+                    // an extra empty row so the algorithm "finishes" the rects that touch the top edge
+                    plate_stack.push(Vec::new());
+
+                    for (y, current_row) in plate_stack.into_iter().enumerate() {
+                        for prev_plate in &prev_row {
+                            if !current_row.contains(prev_plate) {
+                                // remove the finished rect so that the same plate in the future starts a new rect
+                                if let Some(rect) = rect_builder.remove(prev_plate) {
+                                    wall_rects.push(rect);
+                                }
                             }
                         }
+                        for plate in &current_row {
+                            rect_builder
+                                .entry(plate.clone())
+                                .and_modify(|e| e.top += 1)
+                                .or_insert(Rect {
+                                    bottom: y as i32,
+                                    top: y as i32,
+                                    left: plate.left,
+                                    right: plate.right,
+                                });
+                        }
+                        prev_row = current_row;
                     }
-                    for plate in &current_row {
-                        rect_builder
-                            .entry(plate.clone())
-                            .and_modify(|e| e.top += 1)
-                            .or_insert(Rect {
-                                bottom: y as i32,
-                                top: y as i32,
-                                left: plate.left,
-                                right: plate.right,
-                            });
-                    }
-                    prev_row = current_row;
-                }
 
-                commands.entity(level_entity).with_children(|level| {
-                    // Spawn colliders for every rectangle..
-                    // Making the collider a child of the level serves two purposes:
-                    // 1. Adjusts the transforms to be relative to the level for free
-                    // 2. the colliders will be despawned automatically when levels unload
-                    for wall_rect in wall_rects {
-                        level
-                            .spawn_empty()
-                            .insert(Collider::cuboid(
-                                (wall_rect.right as f32 - wall_rect.left as f32 + 1.)
-                                    * grid_size as f32,
-                                (wall_rect.top as f32 - wall_rect.bottom as f32 + 1.)
-                                    * grid_size as f32,
-                            ))
-                            .insert(CollisionGroups::new(Group::GROUP_2, Group::ALL))
-                            .insert(RigidBody::Fixed)
-                            .insert(Friction::new(1.0)) 
-                            .insert(Transform::from_xyz(
-                                (wall_rect.left + wall_rect.right + 1) as f32 * grid_size as f32,
-                                (wall_rect.bottom + wall_rect.top + 1) as f32 * grid_size as f32,
-                                0.,
-                            ))
-                            .insert(GlobalTransform::default());
-                    }
-                });
+                    commands.entity(level_entity).with_children(|level| {
+                        // Spawn colliders for every rectangle..
+                        // Making the collider a child of the level serves two purposes:
+                        // 1. Adjusts the transforms to be relative to the level for free
+                        // 2. the colliders will be despawned automatically when levels unload
+                        for wall_rect in wall_rects {
+                            let mut wall_collider = level.spawn_empty();
+                            if hookable {
+                                wall_collider.insert(Hookable);
+                            }
+                            wall_collider
+                                .insert(Collider::cuboid(
+                                    (wall_rect.right as f32 - wall_rect.left as f32 + 1.)
+                                        * grid_size as f32,
+                                    (wall_rect.top as f32 - wall_rect.bottom as f32 + 1.)
+                                        * grid_size as f32,
+                                ))
+                                .insert(CollisionGroups::new(Group::GROUP_2, Group::ALL))
+                                .insert(RigidBody::Fixed)
+                                .insert(Friction::new(1.0)) 
+                                .insert(Transform::from_xyz(
+                                    (wall_rect.left + wall_rect.right + 1) as f32 * grid_size as f32,
+                                    (wall_rect.bottom + wall_rect.top + 1) as f32 * grid_size as f32,
+                                    0.,
+                                ))
+                                .insert(GlobalTransform::default());
+                        }
+                    });
+                }
             }
         });
     }
@@ -183,7 +199,8 @@ impl Plugin for WallPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Update, spawn_wall_collisions)
             .register_ldtk_int_cell::<WallBundle>(1) //wall
-            .register_ldtk_int_cell::<WallBundle>(3); //clouds
+            .register_ldtk_int_cell::<WallBundle>(3) //clouds
+            .register_ldtk_int_cell::<HookWallBundle>(5); //hookable walls
     }
 }
 